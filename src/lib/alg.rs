@@ -3,6 +3,7 @@ pub mod alg {
 
     use std::cell::RefCell;
     use std::cmp::Reverse;
+    use std::collections::HashMap;
     use std::rc::Rc;
 
     use priority_queue::PriorityQueue;
@@ -11,6 +12,10 @@ pub mod alg {
     pub enum AlgorithmError {
         CannotFindClosestNode,
         CannotFindPath(String),
+        InvalidGraph(String),
+        InvalidArgument(String),
+        DidNotConverge(String),
+        GraphDisconnected(String),
     }
 
     pub fn find_path<T: std::cmp::PartialEq + std::fmt::Display + std::hash::Hash>(
@@ -19,7 +24,7 @@ pub mod alg {
         end: &RefCell<Node<T>>,
     ) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
         // If no nodes exist, return error
-        if graph.get_nodes().len() == 0 {
+        if graph.get_nodes().is_empty() {
             return Err(AlgorithmError::CannotFindPath(
                 "No nodes exist in graph".to_string(),
             ));
@@ -100,7 +105,7 @@ pub mod alg {
                     return Ok(path);
                 }
 
-                let node_found = unvisited_nodes.get(&node);
+                let node_found = unvisited_nodes.get(node);
                 if node_found.is_some() {
                     let distance = closest_node.0 .0.borrow().get_distance() + e.get_weight();
 
@@ -108,7 +113,7 @@ pub mod alg {
                         node.0.borrow_mut().set_distance(distance);
 
                         // Update priority queue
-                        unvisited_nodes.change_priority(&node, Reverse(distance));
+                        unvisited_nodes.change_priority(node, Reverse(distance));
 
                         // Construct path
                         let mut path = Vec::<NodeRcWrapper<T>>::new();
@@ -135,8 +140,611 @@ pub mod alg {
         Err(AlgorithmError::CannotFindPath("No path found".to_string()))
     }
 
+    /// Finds the path from start to end whose smallest edge weight is as large as possible
+    ///
+    /// Edge weights are treated as capacities, so the result is the path able to carry the
+    /// most between start and end. Use calculate_path_bottleneck to get that capacity
+    pub fn find_widest_path<T: std::cmp::PartialEq + std::hash::Hash>(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        end: &RefCell<Node<T>>,
+    ) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
+        let (start, end) = validate_endpoints(graph, start, end)?;
+        let adjacency = graph.get_adjacency();
+
+        // Widest bottleneck found so far for each node, the start is unconstrained
+        let mut width: Vec<Option<u32>> = vec![None; adjacency.len()];
+        let mut previous = vec![None; adjacency.len()];
+        let mut visited = vec![false; adjacency.len()];
+
+        let mut queue = PriorityQueue::<usize, u32>::new();
+        width[start] = Some(u32::MAX);
+        queue.push(start, u32::MAX);
+
+        while let Some((node, node_width)) = queue.pop() {
+            if node == end {
+                return Ok(build_path(graph, &previous, end));
+            }
+            visited[node] = true;
+
+            for &(next, weight) in &adjacency[node] {
+                let candidate = node_width.min(weight);
+
+                if !visited[next] && width[next].is_none_or(|w| candidate > w) {
+                    width[next] = Some(candidate);
+                    previous[next] = Some(node);
+                    queue.push_increase(next, candidate);
+                }
+            }
+        }
+
+        Err(AlgorithmError::CannotFindPath("No path found".to_string()))
+    }
+
+    /// Cheapest path found by find_constrained_path, along with the resources it uses
+    pub struct ConstrainedPath<T: std::cmp::PartialEq + std::hash::Hash> {
+        path: Vec<NodeRcWrapper<T>>,
+        cost: u32,
+        resources: Vec<u32>,
+    }
+
+    impl<T: std::cmp::PartialEq + std::hash::Hash> ConstrainedPath<T> {
+        pub fn get_path(&self) -> &Vec<NodeRcWrapper<T>> {
+            &self.path
+        }
+
+        /// Sum of edge weights along the path, the same as calculate_path_cost
+        pub fn get_cost(&self) -> u32 {
+            self.cost
+        }
+
+        /// Total use of each resource along the path, in the order of the budgets
+        pub fn get_resources(&self) -> &Vec<u32> {
+            &self.resources
+        }
+    }
+
+    /// Finds the cheapest path from start to end that stays within every resource budget
+    ///
+    /// Cost is the sum of edge weights, as in find_path. resources is called with the
+    /// values of both ends of each edge and its weight, and returns how much of each
+    /// resource the edge uses, one entry per budget. Uses label setting, discarding
+    /// partial paths that are no cheaper and use no less of every resource than another
+    /// partial path to the same node
+    pub fn find_constrained_path<T: std::cmp::PartialEq + std::hash::Hash>(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        end: &RefCell<Node<T>>,
+        resources: &dyn Fn(&T, &T, u32) -> Vec<u32>,
+        budgets: &[u32],
+    ) -> Result<ConstrainedPath<T>, AlgorithmError> {
+        let (start, end) = validate_endpoints(graph, start, end)?;
+        let nodes = graph.get_nodes();
+
+        // Resource use of every edge, computed once
+        let mut adjacency = Vec::new();
+        for (i, edges) in graph.get_adjacency().into_iter().enumerate() {
+            let mut with_resources = Vec::new();
+            for (j, weight) in edges {
+                let used = resources(
+                    nodes[i].0.borrow().get_value(),
+                    nodes[j].0.borrow().get_value(),
+                    weight,
+                );
+
+                if used.len() != budgets.len() {
+                    return Err(AlgorithmError::InvalidArgument(
+                        "Resource use does not match the number of budgets".to_string(),
+                    ));
+                }
+
+                with_resources.push((j, weight, used));
+            }
+            adjacency.push(with_resources);
+        }
+
+        // Each label is a partial path: (node, cost, resources, previous label)
+        let mut labels = vec![(start, 0, vec![0; budgets.len()], None)];
+        let mut alive = vec![true];
+        let mut at_node: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        at_node[start].push(0);
+
+        let mut queue = PriorityQueue::<usize, Reverse<u32>>::new();
+        queue.push(0, Reverse(0));
+
+        while let Some((label, _)) = queue.pop() {
+            if !alive[label] {
+                continue;
+            }
+
+            let (node, cost, used, _) = labels[label].clone();
+            if node == end {
+                // Labels come off the queue cheapest first, so this is the best feasible path
+                let mut previous = Vec::new();
+                let mut current = Some(label);
+                while let Some(l) = current {
+                    previous.push(NodeRcWrapper(Rc::clone(&nodes[labels[l].0].0)));
+                    current = labels[l].3;
+                }
+                previous.reverse();
+
+                return Ok(ConstrainedPath {
+                    path: previous,
+                    cost,
+                    resources: used,
+                });
+            }
+
+            for (next, weight, edge_use) in &adjacency[node] {
+                let next_use: Option<Vec<u32>> = used
+                    .iter()
+                    .zip(edge_use)
+                    .map(|(a, b)| a.checked_add(*b))
+                    .collect();
+
+                // Sums past u32::MAX are over any budget, so the label is pruned
+                let (next_cost, next_use) = match (cost.checked_add(*weight), next_use) {
+                    (Some(next_cost), Some(next_use)) => (next_cost, next_use),
+                    _ => continue,
+                };
+
+                if next_use.iter().zip(budgets).any(|(u, b)| u > b) {
+                    continue;
+                }
+
+                if at_node[*next]
+                    .iter()
+                    .any(|&l| labels[l].1 <= next_cost && dominates(&labels[l].2, &next_use))
+                {
+                    continue;
+                }
+
+                // Drop labels the new one dominates
+                for &l in &at_node[*next] {
+                    if next_cost <= labels[l].1 && dominates(&next_use, &labels[l].2) {
+                        alive[l] = false;
+                    }
+                }
+                at_node[*next].retain(|&l| alive[l]);
+
+                labels.push((*next, next_cost, next_use, Some(label)));
+                alive.push(true);
+                at_node[*next].push(labels.len() - 1);
+                queue.push(labels.len() - 1, Reverse(next_cost));
+            }
+        }
+
+        Err(AlgorithmError::CannotFindPath(
+            "No path found within budgets".to_string(),
+        ))
+    }
+
+    /// One path on the Pareto front found by find_pareto_paths
+    pub struct ParetoPath<T: std::cmp::PartialEq + std::hash::Hash> {
+        path: Vec<NodeRcWrapper<T>>,
+        costs: Vec<u32>,
+    }
+
+    impl<T: std::cmp::PartialEq + std::hash::Hash> ParetoPath<T> {
+        pub fn get_path(&self) -> &Vec<NodeRcWrapper<T>> {
+            &self.path
+        }
+
+        /// Total of each cost along the path
+        pub fn get_costs(&self) -> &Vec<u32> {
+            &self.costs
+        }
+    }
+
+    /// Finds every Pareto-optimal path from start to end when edges carry several costs
+    ///
+    /// costs is called with the values of both ends of each edge and its weight, and
+    /// returns the cost vector of the edge, which must have the same length for every
+    /// edge. Include the weight in the vector if it should be one of the objectives. A path
+    /// is returned unless another path is no worse in every cost and better in one, paths
+    /// with identical costs are only returned once. Results are ordered by their costs
+    pub fn find_pareto_paths<T: std::cmp::PartialEq + std::hash::Hash>(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        end: &RefCell<Node<T>>,
+        costs: &dyn Fn(&T, &T, u32) -> Vec<u32>,
+    ) -> Result<Vec<ParetoPath<T>>, AlgorithmError> {
+        let (start, end) = validate_endpoints(graph, start, end)?;
+        let nodes = graph.get_nodes();
+
+        // Cost vector of every edge, computed once
+        let mut adjacency = Vec::new();
+        let mut dimensions = None;
+        for (i, edges) in graph.get_adjacency().into_iter().enumerate() {
+            let mut with_costs = Vec::new();
+            for (j, weight) in edges {
+                let edge_costs = costs(
+                    nodes[i].0.borrow().get_value(),
+                    nodes[j].0.borrow().get_value(),
+                    weight,
+                );
+
+                if *dimensions.get_or_insert(edge_costs.len()) != edge_costs.len() {
+                    return Err(AlgorithmError::InvalidArgument(
+                        "Edge cost vectors have different lengths".to_string(),
+                    ));
+                }
+
+                with_costs.push((j, edge_costs));
+            }
+            adjacency.push(with_costs);
+        }
+        let dimensions = dimensions.unwrap_or(0);
+
+        // Each label is a partial path: (node, costs, previous label)
+        let mut labels = vec![(start, vec![0u32; dimensions], None)];
+        let mut alive = vec![true];
+        let mut at_node: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        at_node[start].push(0);
+
+        // Lexicographic order means a popped label can never be dominated later
+        let mut queue = PriorityQueue::<usize, Reverse<Vec<u32>>>::new();
+        queue.push(0, Reverse(vec![0; dimensions]));
+
+        while let Some((label, _)) = queue.pop() {
+            if !alive[label] {
+                continue;
+            }
+
+            let (node, label_costs, _) = labels[label].clone();
+            if node == end {
+                continue;
+            }
+
+            for (next, edge_costs) in &adjacency[node] {
+                // Costs past u32::MAX cannot be compared, so the label is dropped
+                let next_costs: Vec<u32> = match label_costs
+                    .iter()
+                    .zip(edge_costs)
+                    .map(|(a, b)| a.checked_add(*b))
+                    .collect()
+                {
+                    Some(next_costs) => next_costs,
+                    None => continue,
+                };
+
+                // Skip if dominated at this node or by a path already reaching the end
+                if at_node[*next]
+                    .iter()
+                    .chain(at_node[end].iter())
+                    .any(|&l| dominates(&labels[l].1, &next_costs))
+                {
+                    continue;
+                }
+
+                for &l in &at_node[*next] {
+                    if dominates(&next_costs, &labels[l].1) {
+                        alive[l] = false;
+                    }
+                }
+                at_node[*next].retain(|&l| alive[l]);
+
+                labels.push((*next, next_costs.clone(), Some(label)));
+                alive.push(true);
+                at_node[*next].push(labels.len() - 1);
+                queue.push(labels.len() - 1, Reverse(next_costs));
+            }
+        }
+
+        if at_node[end].is_empty() {
+            return Err(AlgorithmError::CannotFindPath("No path found".to_string()));
+        }
+
+        let mut front: Vec<ParetoPath<T>> = at_node[end]
+            .iter()
+            .map(|&label| {
+                let mut path = Vec::new();
+                let mut current = Some(label);
+                while let Some(l) = current {
+                    path.push(NodeRcWrapper(Rc::clone(&nodes[labels[l].0].0)));
+                    current = labels[l].2;
+                }
+                path.reverse();
+
+                ParetoPath {
+                    path,
+                    costs: labels[label].1.clone(),
+                }
+            })
+            .collect();
+        front.sort_by(|a, b| a.costs.cmp(&b.costs));
+
+        Ok(front)
+    }
+
+    /// Nearest source of every node, created by find_nearest_sources()
+    ///
+    /// Grouping nodes by their nearest source gives a graph Voronoi partition
+    pub struct NearestSources<T: std::cmp::Eq + std::hash::Hash> {
+        sources: HashMap<T, T>,
+        distances: HashMap<T, u32>,
+        partition: HashMap<T, Vec<NodeRcWrapper<T>>>,
+    }
+
+    impl<T: std::cmp::Eq + std::hash::Hash> NearestSources<T> {
+        /// Value of the nearest source of every reached node, keyed by node value
+        pub fn get_sources(&self) -> &HashMap<T, T> {
+            &self.sources
+        }
+
+        /// Distance from the nearest source to every reached node, keyed by node value
+        pub fn get_distances(&self) -> &HashMap<T, u32> {
+            &self.distances
+        }
+
+        /// Nodes nearest to each source, keyed by source value
+        pub fn get_partition(&self) -> &HashMap<T, Vec<NodeRcWrapper<T>>> {
+            &self.partition
+        }
+    }
+
+    /// Dijkstra from several sources at once
+    ///
+    /// Finds the nearest source of every node and the distance from it. Nodes that no
+    /// source reaches within u32::MAX are left out, ties go to the source listed first
+    pub fn find_nearest_sources<T: std::cmp::Eq + std::hash::Hash + Clone>(
+        graph: &Graph<T>,
+        sources: &[&RefCell<Node<T>>],
+    ) -> Result<NearestSources<T>, AlgorithmError> {
+        if sources.is_empty() {
+            return Err(AlgorithmError::InvalidArgument(
+                "Source set is empty".to_string(),
+            ));
+        }
+
+        let indices = sources
+            .iter()
+            .map(|source| {
+                graph.get_node_index(source).ok_or_else(|| {
+                    AlgorithmError::InvalidArgument(
+                        "Source node does not exist in graph".to_string(),
+                    )
+                })
+            })
+            .collect::<Result<Vec<usize>, AlgorithmError>>()?;
+
+        let adjacency = graph.get_adjacency();
+
+        // Each node is labelled with (distance, position of its source in sources)
+        let mut nearest: Vec<Option<(u32, usize)>> = vec![None; adjacency.len()];
+        let mut queue = PriorityQueue::<usize, Reverse<(u32, usize)>>::new();
+        for (rank, &index) in indices.iter().enumerate() {
+            queue.push_increase(index, Reverse((0, rank)));
+        }
+
+        while let Some((node, Reverse((distance, rank)))) = queue.pop() {
+            nearest[node] = Some((distance, rank));
+
+            for &(next, weight) in &adjacency[node] {
+                // Equal distances are settled by the position of the source
+                match distance.checked_add(weight) {
+                    Some(d) if nearest[next].is_none() => {
+                        queue.push_increase(next, Reverse((d, rank)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let nodes = graph.get_nodes();
+        let value = |i: usize| nodes[i].0.borrow().get_value().clone();
+
+        let mut result = NearestSources {
+            sources: HashMap::new(),
+            distances: HashMap::new(),
+            partition: HashMap::new(),
+        };
+        for (i, label) in nearest.into_iter().enumerate() {
+            if let Some((distance, rank)) = label {
+                let source = value(indices[rank]);
+
+                result.sources.insert(value(i), source.clone());
+                result.distances.insert(value(i), distance);
+                result
+                    .partition
+                    .entry(source)
+                    .or_default()
+                    .push(NodeRcWrapper(Rc::clone(&nodes[i].0)));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Finds every node whose shortest distance from start is at most budget
+    ///
+    /// Returns the distance to each of them, keyed by node value and including start at 0.
+    /// The search stops as soon as the next closest node is over the budget
+    pub fn reachable_within<T: std::cmp::Eq + std::hash::Hash + Clone>(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        budget: u32,
+    ) -> Result<HashMap<T, u32>, AlgorithmError> {
+        let start = graph.get_node_index(start).ok_or_else(|| {
+            AlgorithmError::CannotFindPath("Start node does not exist in graph".to_string())
+        })?;
+
+        let adjacency = graph.get_adjacency();
+        let nodes = graph.get_nodes();
+
+        let mut settled = vec![false; adjacency.len()];
+        let mut reached = HashMap::new();
+        let mut queue = PriorityQueue::<usize, Reverse<u32>>::new();
+        queue.push(start, Reverse(0));
+
+        while let Some((node, Reverse(distance))) = queue.pop() {
+            settled[node] = true;
+            reached.insert(nodes[node].0.borrow().get_value().clone(), distance);
+
+            for &(next, weight) in &adjacency[node] {
+                // Nodes over the budget are never queued, so the queue runs dry at the edge
+                match distance.checked_add(weight) {
+                    Some(d) if d <= budget && !settled[next] => {
+                        queue.push_increase(next, Reverse(d));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(reached)
+    }
+
+    /// Returns true if no entry of a is larger than the matching entry of b
+    fn dominates(a: &[u32], b: &[u32]) -> bool {
+        a.iter().zip(b).all(|(x, y)| x <= y)
+    }
+
+    /// Checks start and end the same way find_path does, returning their indices
+    /// in get_nodes()
+    pub(crate) fn validate_endpoints<T: std::cmp::PartialEq + std::hash::Hash>(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        end: &RefCell<Node<T>>,
+    ) -> Result<(usize, usize), AlgorithmError> {
+        if graph.get_nodes().is_empty() {
+            return Err(AlgorithmError::CannotFindPath(
+                "No nodes exist in graph".to_string(),
+            ));
+        }
+
+        if *start.borrow().get_value() == *end.borrow().get_value() {
+            return Err(AlgorithmError::CannotFindPath(
+                "Start and end nodes are the same".to_string(),
+            ));
+        }
+
+        let end = graph.get_node_index(end).ok_or_else(|| {
+            AlgorithmError::CannotFindPath("End node does not exist in graph".to_string())
+        })?;
+
+        let start = graph.get_node_index(start).ok_or_else(|| {
+            AlgorithmError::CannotFindPath("Start node does not exist in graph".to_string())
+        })?;
+
+        Ok((start, end))
+    }
+
+    /// Follows previous back from end to build the path in start to end order
+    pub(crate) fn build_path<T: std::cmp::PartialEq + std::hash::Hash>(
+        graph: &Graph<T>,
+        previous: &[Option<usize>],
+        end: usize,
+    ) -> Vec<NodeRcWrapper<T>> {
+        let nodes = graph.get_nodes();
+
+        let mut path = vec![NodeRcWrapper(Rc::clone(&nodes[end].0))];
+        let mut current = end;
+        while let Some(p) = previous[current] {
+            path.push(NodeRcWrapper(Rc::clone(&nodes[p].0)));
+            current = p;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Dijkstra over an adjacency list from Graph::get_adjacency(), starting from source
+    ///
+    /// Returns the distance to every node, None if the node cannot be reached within
+    /// u32::MAX
+    pub(crate) fn shortest_distances(
+        adjacency: &[Vec<(usize, u32)>],
+        source: usize,
+    ) -> Vec<Option<u32>> {
+        let mut distances = vec![None; adjacency.len()];
+        let mut queue = PriorityQueue::<usize, Reverse<u32>>::new();
+        queue.push(source, Reverse(0));
+
+        while let Some((node, Reverse(distance))) = queue.pop() {
+            distances[node] = Some(distance);
+
+            for &(next, weight) in &adjacency[node] {
+                if distances[next].is_some() {
+                    continue;
+                }
+
+                // push_increase only replaces the priority if the new distance is shorter
+                if let Some(candidate) = distance.checked_add(weight) {
+                    queue.push_increase(next, Reverse(candidate));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Kahn's algorithm over an adjacency list from Graph::get_adjacency()
+    ///
+    /// Returns the nodes in topological order, None if the graph contains a cycle
+    pub(crate) fn topological_order(adjacency: &[Vec<(usize, u32)>]) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; adjacency.len()];
+        for edges in adjacency {
+            for &(to, _) in edges {
+                in_degree[to] += 1;
+            }
+        }
+
+        let mut ready: Vec<usize> = (0..adjacency.len())
+            .filter(|&i| in_degree[i] == 0)
+            .rev()
+            .collect();
+        let mut order = Vec::with_capacity(adjacency.len());
+
+        while let Some(node) = ready.pop() {
+            order.push(node);
+
+            for &(to, _) in &adjacency[node] {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.push(to);
+                }
+            }
+        }
+
+        if order.len() == adjacency.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+
+    /// Orders nodes by repeatedly removing the one with the fewest remaining neighbours
+    pub(crate) fn degeneracy_order<N>(neighbours: &[N]) -> Vec<usize>
+    where
+        for<'a> &'a N: IntoIterator<Item = &'a usize>,
+    {
+        let n = neighbours.len();
+        let mut degree: Vec<usize> = neighbours.iter().map(|s| s.into_iter().count()).collect();
+        let mut removed = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let next = (0..n)
+                .filter(|&i| !removed[i])
+                .min_by_key(|&i| degree[i])
+                .unwrap();
+
+            removed[next] = true;
+            order.push(next);
+
+            for &m in &neighbours[next] {
+                if !removed[m] {
+                    degree[m] -= 1;
+                }
+            }
+        }
+
+        order
+    }
+
     pub fn calculate_path_cost<T: std::cmp::PartialEq + std::fmt::Display + std::hash::Hash>(
-        path: &Vec<NodeRcWrapper<T>>,
+        path: &[NodeRcWrapper<T>],
     ) -> u32 {
         let mut cost = 0;
 
@@ -153,13 +761,38 @@ pub mod alg {
 
         cost
     }
+
+    /// Smallest edge weight along the path, the capacity of a path from find_widest_path
+    ///
+    /// Paths with fewer than two nodes have no edges and return u32::MAX
+    pub fn calculate_path_bottleneck<T: std::cmp::PartialEq + std::hash::Hash>(
+        path: &[NodeRcWrapper<T>],
+    ) -> u32 {
+        let mut bottleneck = u32::MAX;
+
+        for pair in path.windows(2) {
+            let node = pair[0].0.borrow();
+            let next_node = pair[1].0.borrow();
+
+            for e in node.get_edges() {
+                if e.get_node().0.borrow().get_value() == next_node.get_value() {
+                    bottleneck = bottleneck.min(e.get_weight());
+                }
+            }
+        }
+
+        bottleneck
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::graph::{Graph, Node};
 
-    use super::alg::{calculate_path_cost, find_path};
+    use super::alg::{
+        calculate_path_bottleneck, calculate_path_cost, find_constrained_path,
+        find_nearest_sources, find_pareto_paths, find_path, find_widest_path, reachable_within,
+    };
 
     #[test]
     fn simple_directed() {
@@ -211,7 +844,7 @@ mod tests {
                 print!("-> {} ", node.0.borrow().get_value());
             }
         }
-        println!("");
+        println!();
 
         // Expected path: 1 -> 2 -> 5 -> 4 -> 6, cost: 6
         assert_eq!(solution_path.len(), 5, "Path length is not 5");
@@ -328,7 +961,7 @@ mod tests {
                 print!("-> {} ", node.0.borrow().get_value());
             }
         }
-        println!("");
+        println!();
 
         // Expected path: 1 -> 4 -> 6 -> 8 -> 10 -> 11, cost: 10
         assert_eq!(solution_path.len(), 6, "Path length is not 6");
@@ -463,7 +1096,7 @@ mod tests {
                     print!("-> {} ", node.0.borrow().get_value());
                 }
             }
-            println!("");
+            println!();
 
             // Expected directed path: 1 -> 2 -> 5 -> 8 -> 9 -> 6, cost: 8
             // Note: there are multiple cost 8 paths, just picks this due to order nodes are inserted (impacting how they are searched)
@@ -532,4 +1165,342 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn widest_path() {
+        for directed in [true, false] {
+            let mut graph = Graph::<u32>::new(directed);
+            // Add nodes (data is moved into node)
+            let node_1 = Node::new(1);
+            let node_2 = Node::new(2);
+            let node_3 = Node::new(3);
+            let node_4 = Node::new(4);
+            let node_5 = Node::new(5);
+
+            // Add nodes to graph (graph takes ownership of nodes)
+            let node_ptr1 = graph.add_node(node_1).expect("Failed to add node");
+            let node_ptr2 = graph.add_node(node_2).expect("Failed to add node");
+            let node_ptr3 = graph.add_node(node_3).expect("Failed to add node");
+            let node_ptr4 = graph.add_node(node_4).expect("Failed to add node");
+            let node_ptr5 = graph.add_node(node_5).expect("Failed to add node");
+
+            // Add edges, weights are capacities
+
+            // 1 -> 2, 10
+            graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 10);
+            // 2 -> 5, 1
+            graph.add_edge(node_ptr2.0.as_ref(), node_ptr5.0.as_ref(), 1);
+            // 1 -> 3, 4
+            graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 4);
+            // 3 -> 4, 6
+            graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 6);
+            // 4 -> 5, 5
+            graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 5);
+
+            // Run algorithm
+            let solution_path =
+                find_widest_path(&graph, node_ptr1.0.as_ref(), node_ptr5.0.as_ref());
+
+            assert!(solution_path.is_ok());
+
+            let solution_path = solution_path.unwrap();
+
+            // Expected path: 1 -> 3 -> 4 -> 5, bottleneck: 4
+            let values: Vec<u32> = solution_path
+                .iter()
+                .map(|n| *n.0.borrow().get_value())
+                .collect();
+            assert_eq!(values, vec![1, 3, 4, 5], "Widest path incorrect");
+
+            let bottleneck = calculate_path_bottleneck(&solution_path);
+            assert_eq!(bottleneck, 4, "Path bottleneck is not 4");
+
+            // The shortest path by cost goes through the narrow 2 -> 5 link instead
+            let shortest = find_path(&mut graph, node_ptr1.0.as_ref(), node_ptr5.0.as_ref());
+            assert_eq!(
+                calculate_path_bottleneck(&shortest.unwrap()),
+                1,
+                "Shortest path bottleneck is not 1"
+            );
+
+            // Paths without edges are unconstrained
+            assert_eq!(calculate_path_bottleneck::<u32>(&[]), u32::MAX);
+            assert_eq!(calculate_path_bottleneck(&solution_path[..1]), u32::MAX);
+        }
+    }
+
+    #[test]
+    fn constrained_path() {
+        let mut graph = Graph::<u32>::new(true);
+        // Add nodes (data is moved into node)
+        let node_1 = Node::new(1);
+        let node_2 = Node::new(2);
+        let node_3 = Node::new(3);
+        let node_4 = Node::new(4);
+
+        // Add nodes to graph (graph takes ownership of nodes)
+        let node_ptr1 = graph.add_node(node_1).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(node_2).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(node_3).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(node_4).expect("Failed to add node");
+
+        // Add edges
+
+        // 1 -> 2, 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        // 2 -> 4, 1
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        // 1 -> 3, 2
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 2);
+        // 3 -> 4, 2
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 2);
+
+        // Edges through node 2 burn 5 fuel each, all others burn 1
+        let fuel = |from: &u32, to: &u32, _: u32| {
+            if *from == 2 || *to == 2 {
+                vec![5]
+            } else {
+                vec![1]
+            }
+        };
+
+        // Enough fuel for the cheap path 1 -> 2 -> 4
+        let result = find_constrained_path(
+            &graph,
+            node_ptr1.0.as_ref(),
+            node_ptr4.0.as_ref(),
+            &fuel,
+            &[10],
+        )
+        .unwrap();
+        assert_eq!(result.get_path().len(), 3, "Path length is not 3");
+        assert_eq!(result.get_cost(), 2, "Path cost is not 2");
+        assert_eq!(result.get_resources(), &vec![10], "Fuel use is not 10");
+
+        // Not enough fuel, so take 1 -> 3 -> 4
+        let result = find_constrained_path(
+            &graph,
+            node_ptr1.0.as_ref(),
+            node_ptr4.0.as_ref(),
+            &fuel,
+            &[5],
+        )
+        .unwrap();
+        assert_eq!(
+            *result.get_path()[1].0.borrow().get_value(),
+            3,
+            "Path incorrect"
+        );
+        assert_eq!(result.get_cost(), 4, "Path cost is not 4");
+        assert_eq!(calculate_path_cost(result.get_path()), 4);
+
+        // No path fits in a budget of 1
+        let result = find_constrained_path(
+            &graph,
+            node_ptr1.0.as_ref(),
+            node_ptr4.0.as_ref(),
+            &fuel,
+            &[1],
+        );
+        assert!(result.is_err(), "Path found over budget");
+
+        // Fuel through node 2 overflows u32, so only 1 -> 3 -> 4 fits
+        let heavy = |from: &u32, to: &u32, _: u32| {
+            if *from == 2 || *to == 2 {
+                vec![u32::MAX]
+            } else {
+                vec![1]
+            }
+        };
+        let result = find_constrained_path(
+            &graph,
+            node_ptr1.0.as_ref(),
+            node_ptr4.0.as_ref(),
+            &heavy,
+            &[u32::MAX],
+        )
+        .unwrap();
+        assert_eq!(result.get_cost(), 4, "Overflowing path not pruned");
+    }
+
+    #[test]
+    fn pareto_paths() {
+        let mut graph = Graph::<u32>::new(false);
+        // Add nodes (data is moved into node)
+        let node_1 = Node::new(1);
+        let node_2 = Node::new(2);
+        let node_3 = Node::new(3);
+        let node_4 = Node::new(4);
+        let node_5 = Node::new(5);
+
+        // Add nodes to graph (graph takes ownership of nodes)
+        let node_ptr1 = graph.add_node(node_1).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(node_2).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(node_3).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(node_4).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(node_5).expect("Failed to add node");
+
+        // Add edges, weights are distances
+
+        // 1 - 2, 1 (toll road)
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        // 2 - 5, 1 (toll road)
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr5.0.as_ref(), 1);
+        // 1 - 3, 3
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 3);
+        // 3 - 5, 3
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr5.0.as_ref(), 3);
+        // 1 - 4, 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 4);
+        // 4 - 5, 4
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 4);
+
+        // Costs are (distance, toll), roads touching 2 cost 5 in tolls
+        let costs = |from: &u32, to: &u32, weight: u32| {
+            let toll = if *from == 2 || *to == 2 { 5 } else { 0 };
+            vec![weight, toll]
+        };
+
+        let front =
+            find_pareto_paths(&graph, node_ptr1.0.as_ref(), node_ptr5.0.as_ref(), &costs).unwrap();
+
+        // 1 -> 2 -> 5 is fastest, 1 -> 3 -> 5 is free, 1 -> 4 -> 5 is dominated by 1 -> 3 -> 5
+        assert_eq!(front.len(), 2, "Pareto front does not have 2 paths");
+        assert_eq!(
+            front[0].get_costs(),
+            &vec![2, 10],
+            "Fastest path costs incorrect"
+        );
+        assert_eq!(
+            front[1].get_costs(),
+            &vec![6, 0],
+            "Free path costs incorrect"
+        );
+        assert_eq!(
+            *front[1].get_path()[1].0.borrow().get_value(),
+            3,
+            "Free path incorrect"
+        );
+
+        // Tolls through 2 overflow u32, leaving only the free path
+        let overflowing = |from: &u32, to: &u32, weight: u32| {
+            let toll = if *from == 2 || *to == 2 { u32::MAX } else { 0 };
+            vec![weight, toll]
+        };
+        let front = find_pareto_paths(
+            &graph,
+            node_ptr1.0.as_ref(),
+            node_ptr5.0.as_ref(),
+            &overflowing,
+        )
+        .unwrap();
+        assert_eq!(front.len(), 1, "Overflowing path not dropped");
+        assert_eq!(front[0].get_costs(), &vec![6, 0]);
+    }
+
+    #[test]
+    fn nearest_sources() {
+        let mut graph = Graph::<u32>::new(false);
+        // Add nodes (data is moved into node)
+        let node_1 = Node::new(1);
+        let node_2 = Node::new(2);
+        let node_3 = Node::new(3);
+        let node_4 = Node::new(4);
+        let node_5 = Node::new(5);
+        let node_6 = Node::new(6);
+
+        // Add nodes to graph (graph takes ownership of nodes)
+        let node_ptr1 = graph.add_node(node_1).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(node_2).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(node_3).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(node_4).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(node_5).expect("Failed to add node");
+        graph.add_node(node_6).expect("Failed to add node");
+
+        // Add edges, 6 is isolated
+
+        // 1 - 2, 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        // 2 - 3, 1
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        // 3 - 4, 1
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        // 4 - 5, 1
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 1);
+
+        let nearest =
+            find_nearest_sources(&graph, &[node_ptr1.0.as_ref(), node_ptr5.0.as_ref()]).unwrap();
+
+        // 3 is as close to 1 as to 5, so it goes to 1 which is listed first
+        assert_eq!(nearest.get_sources()[&3], 1, "Nearest source of 3 is not 1");
+        assert_eq!(nearest.get_sources()[&4], 5, "Nearest source of 4 is not 5");
+        assert_eq!(nearest.get_distances()[&3], 2, "Distance to 3 is not 2");
+        assert_eq!(nearest.get_distances()[&5], 0, "Distance to 5 is not 0");
+        assert!(
+            !nearest.get_sources().contains_key(&6),
+            "Isolated node has a source"
+        );
+
+        assert_eq!(
+            nearest.get_partition()[&1].len(),
+            3,
+            "Cell of 1 is not 1, 2 and 3"
+        );
+        assert_eq!(
+            nearest.get_partition()[&5].len(),
+            2,
+            "Cell of 5 is not 4 and 5"
+        );
+
+        // 1 -> 2 -> 3 is longer than u32::MAX, so 3 is not reached
+        let mut far = Graph::<u32>::new(true);
+        let node_ptr1 = far.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = far.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = far.add_node(Node::new(3)).expect("Failed to add node");
+        far.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX);
+        far.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        let nearest = find_nearest_sources(&far, &[node_ptr1.0.as_ref()]).unwrap();
+        assert_eq!(nearest.get_distances()[&2], u32::MAX);
+        assert!(
+            !nearest.get_sources().contains_key(&3),
+            "Node past u32::MAX has a source"
+        );
+    }
+
+    #[test]
+    fn within_budget() {
+        let mut graph = Graph::<u32>::new(true);
+        // Add nodes (data is moved into node)
+        let node_1 = Node::new(1);
+        let node_2 = Node::new(2);
+        let node_3 = Node::new(3);
+        let node_4 = Node::new(4);
+
+        // Add nodes to graph (graph takes ownership of nodes)
+        let node_ptr1 = graph.add_node(node_1).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(node_2).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(node_3).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(node_4).expect("Failed to add node");
+
+        // Add edges
+
+        // 1 -> 2, 5
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 5);
+        // 1 -> 3, 20
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 20);
+        // 2 -> 3, 10
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 10);
+        // 3 -> 4, 1
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let reached = reachable_within(&graph, node_ptr1.0.as_ref(), 15).unwrap();
+
+        // 3 is reached in 15 through 2, 4 is one step too far
+        assert_eq!(reached.len(), 3, "Reached nodes are not 1, 2 and 3");
+        assert_eq!(reached[&1], 0, "Distance to start is not 0");
+        assert_eq!(reached[&2], 5, "Distance to 2 is not 5");
+        assert_eq!(reached[&3], 15, "Distance to 3 is not 15");
+        assert!(!reached.contains_key(&4), "4 is over the budget");
+    }
 }
@@ -0,0 +1,287 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Minimum spanning arborescence of a directed graph, created by minimum_arborescence()
+pub struct Arborescence<T: std::cmp::PartialEq + std::hash::Hash> {
+    tree: Graph<T>,
+    cost: u32,
+    unreachable: Vec<NodeRcWrapper<T>>,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> Arborescence<T> {
+    /// Directed tree over the nodes reachable from the root, with edges pointing away from it
+    pub fn get_tree(&self) -> &Graph<T> {
+        &self.tree
+    }
+
+    /// Total weight of the tree edges
+    pub fn get_cost(&self) -> u32 {
+        self.cost
+    }
+
+    /// Nodes of the graph that the root cannot reach, and so are not in the tree
+    pub fn get_unreachable(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.unreachable
+    }
+}
+
+/// Finds the cheapest set of edges connecting root to every node it can reach
+///
+/// Uses Chu-Liu/Edmonds. Every reachable node other than the root gets exactly one incoming
+/// tree edge. Nodes the root cannot reach are left out of the tree and reported instead
+pub fn minimum_arborescence<T: std::cmp::PartialEq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    root: &RefCell<Node<T>>,
+) -> Result<Arborescence<T>, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    let root = graph.get_node_index(root).ok_or_else(|| {
+        AlgorithmError::InvalidArgument("Root node does not exist in graph".to_string())
+    })?;
+
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+
+    let mut reached = vec![false; n];
+    reached[root] = true;
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        for &(next, _) in &adjacency[node] {
+            if !reached[next] {
+                reached[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    // Only reachable nodes take part, renumbered from 0
+    let kept: Vec<usize> = (0..n).filter(|&i| reached[i]).collect();
+    let mut compact = vec![0; n];
+    for (c, &i) in kept.iter().enumerate() {
+        compact[i] = c;
+    }
+
+    let edges: Vec<(usize, usize, u32)> = kept
+        .iter()
+        .flat_map(|&u| {
+            adjacency[u]
+                .iter()
+                .map(|&(v, weight)| (compact[u], compact[v], weight))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let nodes = graph.get_nodes();
+    let mut tree = Graph::new(true);
+    let tree_nodes: Vec<NodeRcWrapper<T>> = kept
+        .iter()
+        .map(|&i| {
+            tree.add_node(Node::new(nodes[i].0.borrow().get_value().clone()))
+                .unwrap()
+        })
+        .collect();
+
+    let mut cost: u32 = 0;
+    for i in chu_liu_edmonds(kept.len(), compact[root], &edges) {
+        let (u, v, weight) = edges[i];
+        tree.add_edge(tree_nodes[u].0.as_ref(), tree_nodes[v].0.as_ref(), weight);
+        cost = cost.checked_add(weight).ok_or_else(|| {
+            AlgorithmError::InvalidGraph("Arborescence cost exceeds u32::MAX".to_string())
+        })?;
+    }
+
+    Ok(Arborescence {
+        tree,
+        cost,
+        unreachable: nodes
+            .iter()
+            .zip(&reached)
+            .filter(|(_, &r)| !r)
+            .map(|(n, _)| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect(),
+    })
+}
+
+/// Returns the positions in edges of the arborescence edges
+///
+/// Every node must be reachable from root. Picks the cheapest edge into every node, and if
+/// those form cycles, contracts each cycle into one node and solves the smaller graph
+fn chu_liu_edmonds(n: usize, root: usize, edges: &[(usize, usize, u32)]) -> Vec<usize> {
+    let mut incoming: Vec<Option<usize>> = vec![None; n];
+    for (i, &(u, v, weight)) in edges.iter().enumerate() {
+        if u != v && v != root && incoming[v].is_none_or(|j| weight < edges[j].2) {
+            incoming[v] = Some(i);
+        }
+    }
+
+    // Follow incoming edges back from every node, a walk that meets itself found a cycle
+    let mut component: Vec<Option<usize>> = vec![None; n];
+    let mut on_cycle = vec![false; n];
+    let mut walked_by = vec![None; n];
+    let mut count = 0;
+
+    for start in 0..n {
+        let mut v = start;
+        while v != root && walked_by[v].is_none() {
+            walked_by[v] = Some(start);
+            v = edges[incoming[v].unwrap()].0;
+        }
+
+        if v != root && walked_by[v] == Some(start) && component[v].is_none() {
+            let mut u = v;
+            loop {
+                component[u] = Some(count);
+                on_cycle[u] = true;
+                u = edges[incoming[u].unwrap()].0;
+                if u == v {
+                    break;
+                }
+            }
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return (0..n)
+            .filter(|&v| v != root)
+            .map(|v| incoming[v].unwrap())
+            .collect();
+    }
+
+    let component: Vec<usize> = component
+        .into_iter()
+        .map(|c| {
+            c.unwrap_or_else(|| {
+                count += 1;
+                count - 1
+            })
+        })
+        .collect();
+
+    // Entering a cycle at v replaces the cycle edge into v, so only the difference counts
+    let mut contracted = Vec::new();
+    let mut origin = Vec::new();
+    for (i, &(u, v, weight)) in edges.iter().enumerate() {
+        if component[u] != component[v] {
+            let replaced = if on_cycle[v] {
+                edges[incoming[v].unwrap()].2
+            } else {
+                0
+            };
+
+            contracted.push((component[u], component[v], weight - replaced));
+            origin.push(i);
+        }
+    }
+
+    let mut chosen = Vec::new();
+    let mut entered = vec![false; n];
+    for c in chu_liu_edmonds(count, component[root], &contracted) {
+        chosen.push(origin[c]);
+        entered[edges[origin[c]].1] = true;
+    }
+
+    // Keep every cycle edge except the one into the node where the cycle is entered
+    for v in 0..n {
+        if on_cycle[v] && !entered[v] {
+            chosen.push(incoming[v].unwrap());
+        }
+    }
+
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::minimum_arborescence;
+
+    #[test]
+    fn contracts_cycle() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+
+        // 2 <-> 3 is the cheapest way into both, so they form a cycle to break
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 10);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 5);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        // 2 -> 5 and 3 -> 5
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr5.0.as_ref(), 3);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr5.0.as_ref(), 4);
+        // 4 -> 1, nothing reaches 4
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr1.0.as_ref(), 1);
+
+        let arborescence = minimum_arborescence(&graph, node_ptr1.0.as_ref()).unwrap();
+
+        // 1 -> 3 -> 2 -> 5
+        assert_eq!(arborescence.get_cost(), 9, "Arborescence cost is not 9");
+        let tree = arborescence.get_tree();
+        assert_eq!(tree.get_nodes().len(), 4, "Tree does not have 4 nodes");
+
+        let node = tree.get_node(node_ptr3.0.as_ref()).unwrap();
+        let children: Vec<u32> = node
+            .0
+            .borrow()
+            .get_edges()
+            .iter()
+            .map(|e| *e.get_node().0.borrow().get_value())
+            .collect();
+        assert_eq!(children, vec![2], "Children of 3 are not 2");
+
+        let unreachable = arborescence.get_unreachable();
+        assert_eq!(unreachable.len(), 1, "Unreachable nodes are not 4");
+        assert_eq!(
+            *unreachable[0].0.borrow().get_value(),
+            4,
+            "Unreachable nodes are not 4"
+        );
+    }
+
+    #[test]
+    fn undirected_rejected() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+
+        // 1 - 2
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        assert!(
+            minimum_arborescence(&graph, node_ptr1.0.as_ref()).is_err(),
+            "Undirected graph was accepted"
+        );
+    }
+
+    #[test]
+    fn cost_overflow_rejected() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 -> 2 and 1 -> 3 are both needed and together exceed u32::MAX
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        assert!(
+            minimum_arborescence(&graph, node_ptr1.0.as_ref()).is_err(),
+            "Cost past u32::MAX was accepted"
+        );
+    }
+}
@@ -0,0 +1,622 @@
+use crate::alg::alg::{shortest_distances, AlgorithmError};
+use crate::graph::Graph;
+
+use std::collections::HashMap;
+
+/// Number of edges touching each node, keyed by node value
+///
+/// For directed graphs this is the sum of the in and out degree.
+/// If normalized, values are divided by the number of other nodes (n - 1)
+pub fn degree_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let adjacency = graph.get_adjacency();
+
+    let mut degrees: Vec<f64> = adjacency.iter().map(|a| a.len() as f64).collect();
+    if graph.is_directed() {
+        for (i, d) in in_degrees(&adjacency).into_iter().enumerate() {
+            degrees[i] += d;
+        }
+    }
+
+    degree_scores(graph, degrees, normalized)
+}
+
+/// Number of edges entering each node, keyed by node value
+///
+/// For undirected graphs this is the same as degree_centrality
+pub fn in_degree_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let adjacency = graph.get_adjacency();
+
+    degree_scores(graph, in_degrees(&adjacency), normalized)
+}
+
+/// Number of edges leaving each node, keyed by node value
+///
+/// For undirected graphs this is the same as degree_centrality
+pub fn out_degree_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let adjacency = graph.get_adjacency();
+    let degrees = adjacency.iter().map(|a| a.len() as f64).collect();
+
+    degree_scores(graph, degrees, normalized)
+}
+
+/// Closeness centrality using weighted shortest path distances from each node
+///
+/// The score of a node is the reciprocal of the sum of distances to every node it can reach.
+/// If normalized, the score is instead (r - 1) / sum, scaled by (r - 1) / (n - 1) where r is
+/// the number of reachable nodes (including itself), so nodes in small components are not
+/// favoured. Nodes that reach nothing score 0
+pub fn closeness_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+
+    let mut scores = vec![0.0; n];
+    for (i, score) in scores.iter_mut().enumerate() {
+        let distances = shortest_distances(&adjacency, i);
+
+        let reachable = distances.iter().filter(|d| d.is_some()).count();
+        let total: f64 = distances.iter().flatten().map(|&d| d as f64).sum();
+
+        if total > 0.0 {
+            *score = if normalized {
+                let others = (reachable - 1) as f64;
+                (others / total) * (others / (n - 1) as f64)
+            } else {
+                1.0 / total
+            };
+        }
+    }
+
+    graph.key_by_value(scores)
+}
+
+/// Betweenness centrality computed with Brandes' algorithm, using edge weights as distances
+///
+/// The score of a node is the sum, over all pairs of other nodes, of the fraction of
+/// shortest paths between the pair that pass through it. For undirected graphs each
+/// pair is only counted once. If normalized, scores are divided by the number of pairs
+/// not containing the node
+pub fn betweenness_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+
+    let mut scores = vec![0.0; n];
+    for source in 0..n {
+        let (order, predecessors, paths) = shortest_path_dag(&adjacency, source);
+
+        // Accumulate dependencies, furthest nodes first
+        let mut dependency = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                dependency[v] += paths[v] / paths[w] * (1.0 + dependency[w]);
+            }
+
+            if w != source {
+                scores[w] += dependency[w];
+            }
+        }
+    }
+
+    // Every ordered pair was counted, both sides are the same pair when undirected
+    let scale = if normalized {
+        if n > 2 {
+            1.0 / ((n - 1) * (n - 2)) as f64
+        } else {
+            1.0
+        }
+    } else if graph.is_directed() {
+        1.0
+    } else {
+        0.5
+    };
+
+    for s in scores.iter_mut() {
+        *s *= scale;
+    }
+
+    graph.key_by_value(scores)
+}
+
+/// Settings for eigenvector_centrality
+///
+/// Defaults are a tolerance of 1e-6 and 1000 iterations
+pub struct EigenvectorConfig {
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl EigenvectorConfig {
+    pub fn new() -> EigenvectorConfig {
+        EigenvectorConfig {
+            tolerance: 1e-6,
+            max_iterations: 1000,
+        }
+    }
+
+    /// Iteration stops once the total change of all scores is below n * tolerance
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+}
+
+impl Default for EigenvectorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Eigenvector centrality by power iteration, using edge weights, keyed by node value
+///
+/// A node scores highly when it is linked to from other high scoring nodes. For directed
+/// graphs only incoming edges count. Each step adds the current scores to the incoming
+/// sums so the iteration settles on bipartite graphs too. Scores have a Euclidean norm of 1
+pub fn eigenvector_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    config: &EigenvectorConfig,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+    if n == 0 {
+        return Err(AlgorithmError::InvalidGraph(
+            "No nodes exist in graph".to_string(),
+        ));
+    }
+
+    let mut scores = vec![1.0 / (n as f64).sqrt(); n];
+    for _ in 0..config.max_iterations {
+        let mut next = scores.clone();
+        for (u, edges) in adjacency.iter().enumerate() {
+            for &(v, weight) in edges {
+                next[v] += scores[u] * weight as f64;
+            }
+        }
+
+        // Never 0, since each node keeps its own score
+        let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for x in next.iter_mut() {
+            *x /= norm;
+        }
+
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+
+        if change < n as f64 * config.tolerance {
+            return Ok(graph.key_by_value(scores));
+        }
+    }
+
+    Err(AlgorithmError::DidNotConverge(format!(
+        "Eigenvector centrality did not converge in {} iterations",
+        config.max_iterations
+    )))
+}
+
+/// Settings for katz_centrality
+///
+/// Defaults are an attenuation factor of 0.1, a base score of 1, a tolerance of 1e-6,
+/// 1000 iterations and normalized scores
+pub struct KatzConfig {
+    alpha: f64,
+    beta: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    normalized: bool,
+}
+
+impl KatzConfig {
+    pub fn new() -> KatzConfig {
+        KatzConfig {
+            alpha: 0.1,
+            beta: 1.0,
+            tolerance: 1e-6,
+            max_iterations: 1000,
+            normalized: true,
+        }
+    }
+
+    /// Weight given to each extra step of a walk, must be below 1 / the largest
+    /// eigenvalue of the adjacency matrix for the scores to converge
+    pub fn set_alpha(&mut self, alpha: f64) {
+        self.alpha = alpha;
+    }
+
+    /// Score every node gets regardless of its links
+    pub fn set_beta(&mut self, beta: f64) {
+        self.beta = beta;
+    }
+
+    /// Iteration stops once the total change of all scores is below n * tolerance
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    /// If set, scores are scaled to a Euclidean norm of 1
+    pub fn set_normalized(&mut self, normalized: bool) {
+        self.normalized = normalized;
+    }
+}
+
+impl Default for KatzConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Katz centrality using edge weights, keyed by node value
+///
+/// Counts the walks ending at each node, each step along a walk multiplying its
+/// contribution by alpha and the edge weight. For directed graphs only incoming
+/// edges count
+pub fn katz_centrality<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    config: &KatzConfig,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+    if n == 0 {
+        return Err(AlgorithmError::InvalidGraph(
+            "No nodes exist in graph".to_string(),
+        ));
+    }
+
+    let mut scores = vec![0.0; n];
+    for _ in 0..config.max_iterations {
+        let mut next = vec![config.beta; n];
+        for (u, edges) in adjacency.iter().enumerate() {
+            for &(v, weight) in edges {
+                next[v] += config.alpha * scores[u] * weight as f64;
+            }
+        }
+
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+
+        if change < n as f64 * config.tolerance {
+            if config.normalized {
+                let norm = scores.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    for x in scores.iter_mut() {
+                        *x /= norm;
+                    }
+                }
+            }
+
+            return Ok(graph.key_by_value(scores));
+        }
+    }
+
+    Err(AlgorithmError::DidNotConverge(format!(
+        "Katz centrality did not converge in {} iterations",
+        config.max_iterations
+    )))
+}
+
+/// Every shortest path from source, found from Dijkstra distances
+///
+/// Returns the reachable nodes in an order where each comes after its predecessors on
+/// shortest paths, those predecessors and the number of shortest paths reaching each node.
+/// Zero weight edges join nodes at the same distance, so the order follows the edges
+/// rather than the distances. A zero weight cycle is broken at its closest node
+fn shortest_path_dag(
+    adjacency: &[Vec<(usize, u32)>],
+    source: usize,
+) -> (Vec<usize>, Vec<Vec<usize>>, Vec<f64>) {
+    let n = adjacency.len();
+    let distances = shortest_distances(adjacency, source);
+
+    // An edge is on a shortest path when it leads to exactly the distance of its end
+    let mut predecessors = vec![Vec::new(); n];
+    let mut successors = vec![Vec::new(); n];
+    for (v, edges) in adjacency.iter().enumerate() {
+        for &(w, weight) in edges {
+            let reached = distances[v].and_then(|d| d.checked_add(weight));
+            if w != v && w != source && reached.is_some() && reached == distances[w] {
+                predecessors[w].push(v);
+                successors[v].push(w);
+            }
+        }
+    }
+
+    let mut waiting: Vec<usize> = predecessors.iter().map(|p| p.len()).collect();
+    let mut ordered = vec![false; n];
+    let mut order = Vec::new();
+    let mut paths = vec![0.0; n];
+    let mut ready = vec![source];
+
+    loop {
+        let v = match ready.pop() {
+            Some(v) => v,
+            None => {
+                // Only nodes behind a zero weight cycle are left
+                let next = (0..n)
+                    .filter(|&v| !ordered[v] && predecessors[v].iter().any(|&p| ordered[p]))
+                    .min_by_key(|&v| distances[v]);
+
+                match next {
+                    Some(v) => {
+                        predecessors[v].retain(|&p| ordered[p]);
+                        v
+                    }
+                    None => break,
+                }
+            }
+        };
+
+        ordered[v] = true;
+        order.push(v);
+        paths[v] = if v == source {
+            1.0
+        } else {
+            predecessors[v].iter().map(|&p| paths[p]).sum()
+        };
+
+        for &w in &successors[v] {
+            if !ordered[w] {
+                waiting[w] -= 1;
+                if waiting[w] == 0 {
+                    ready.push(w);
+                }
+            }
+        }
+    }
+
+    (order, predecessors, paths)
+}
+
+fn in_degrees(adjacency: &[Vec<(usize, u32)>]) -> Vec<f64> {
+    let mut degrees = vec![0.0; adjacency.len()];
+    for edges in adjacency {
+        for &(to, _) in edges {
+            degrees[to] += 1.0;
+        }
+    }
+
+    degrees
+}
+
+/// Keys degree scores by node value, dividing by n - 1 if normalized
+fn degree_scores<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    degrees: Vec<f64>,
+    normalized: bool,
+) -> HashMap<T, f64> {
+    let n = graph.get_nodes().len();
+    let scale = if normalized && n > 1 {
+        1.0 / (n - 1) as f64
+    } else {
+        1.0
+    };
+
+    graph.key_by_value(degrees.into_iter().map(|d| d * scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alg::alg::AlgorithmError;
+    use crate::graph::{Graph, Node};
+
+    use super::{
+        betweenness_centrality, closeness_centrality, degree_centrality, eigenvector_centrality,
+        in_degree_centrality, katz_centrality, out_degree_centrality, EigenvectorConfig,
+        KatzConfig,
+    };
+
+    #[test]
+    fn undirected_path() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 - 2 - 3 - 4, all weight 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let degree = degree_centrality(&graph, false);
+        assert_eq!(degree[&1], 1.0, "Degree of 1 is not 1");
+        assert_eq!(degree[&2], 2.0, "Degree of 2 is not 2");
+
+        // Node 2 lies on the paths 1-3 and 1-4
+        let betweenness = betweenness_centrality(&graph, false);
+        assert_eq!(betweenness[&1], 0.0, "Betweenness of 1 is not 0");
+        assert_eq!(betweenness[&2], 2.0, "Betweenness of 2 is not 2");
+        assert_eq!(betweenness[&3], 2.0, "Betweenness of 3 is not 2");
+
+        let betweenness = betweenness_centrality(&graph, true);
+        assert!(
+            (betweenness[&2] - 2.0 / 3.0).abs() < 1e-9,
+            "Normalized betweenness of 2 is not 2/3"
+        );
+
+        // Distances from 1 sum to 1 + 2 + 3, from 2 sum to 1 + 1 + 2
+        let closeness = closeness_centrality(&graph, true);
+        assert!(
+            (closeness[&1] - 0.5).abs() < 1e-9,
+            "Closeness of 1 is not 0.5"
+        );
+        assert!(
+            (closeness[&2] - 0.75).abs() < 1e-9,
+            "Closeness of 2 is not 0.75"
+        );
+    }
+
+    #[test]
+    fn distances_past_u32_max() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 -> 2 -> 3 is longer than u32::MAX, so 3 counts as unreachable from 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX - 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 5);
+
+        let closeness = closeness_centrality(&graph, false);
+        assert_eq!(
+            closeness[&1],
+            1.0 / (u32::MAX - 1) as f64,
+            "Closeness of 1 counts 3"
+        );
+        assert_eq!(closeness[&2], 0.2, "Closeness of 2 is not 1 / 5");
+
+        let betweenness = betweenness_centrality(&graph, false);
+        assert_eq!(betweenness[&2], 0.0, "Betweenness of 2 is not 0");
+    }
+
+    #[test]
+    fn weighted_betweenness() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 4 costs 2, 1 -> 3 -> 4 costs 2, 1 -> 4 costs 5
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 5);
+
+        // Both 2 and 3 carry half of the shortest paths from 1 to 4
+        let betweenness = betweenness_centrality(&graph, false);
+        assert_eq!(betweenness[&2], 0.5, "Betweenness of 2 is not 0.5");
+        assert_eq!(betweenness[&3], 0.5, "Betweenness of 3 is not 0.5");
+        assert_eq!(betweenness[&1], 0.0, "Betweenness of 1 is not 0");
+
+        let in_degree = in_degree_centrality(&graph, false);
+        let out_degree = out_degree_centrality(&graph, false);
+        assert_eq!(in_degree[&4], 3.0, "In degree of 4 is not 3");
+        assert_eq!(out_degree[&4], 0.0, "Out degree of 4 is not 0");
+        assert_eq!(out_degree[&1], 3.0, "Out degree of 1 is not 3");
+
+        let degree = degree_centrality(&graph, true);
+        assert_eq!(degree[&1], 1.0, "Normalized degree of 1 is not 1");
+    }
+
+    #[test]
+    fn zero_weight_betweenness() {
+        // The result must not depend on which of 1 and 2 is settled first
+        for values in [[0, 1, 2], [0, 2, 1]] {
+            let mut graph = Graph::<u32>::new(true);
+            let nodes: Vec<_> = values
+                .iter()
+                .map(|&v| graph.add_node(Node::new(v)).expect("Failed to add node"))
+                .collect();
+            let node = |v: u32| {
+                let i = values.iter().position(|&x| x == v).unwrap();
+                nodes[i].0.as_ref()
+            };
+
+            // 0 -> 1 costs 1 directly and 1 through 2 over a free edge
+            graph.add_edge(node(0), node(1), 1);
+            graph.add_edge(node(0), node(2), 1);
+            graph.add_edge(node(2), node(1), 0);
+
+            let betweenness = betweenness_centrality(&graph, false);
+            assert_eq!(betweenness[&2], 0.5, "Betweenness of 2 is not 0.5");
+            assert_eq!(betweenness[&1], 0.0, "Betweenness of 1 is not 0");
+        }
+    }
+
+    #[test]
+    fn eigenvector_and_katz() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // Star with 1 in the middle
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        // The center is sqrt(3) times each leaf
+        let mut config = EigenvectorConfig::new();
+        config.set_tolerance(1e-9);
+        let eigenvector = eigenvector_centrality(&graph, &config).unwrap();
+        assert!(
+            (eigenvector[&1] - 0.5f64.sqrt()).abs() < 1e-6,
+            "Eigenvector centrality of 1 is not sqrt(1/2)"
+        );
+        assert!(
+            (eigenvector[&2] - (1.0f64 / 6.0).sqrt()).abs() < 1e-6,
+            "Eigenvector centrality of 2 is not sqrt(1/6)"
+        );
+
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 3 weighs 2, 2 -> 3 and 3 -> 4 weigh 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 2);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let mut config = KatzConfig::new();
+        config.set_normalized(false);
+        let katz = katz_centrality(&graph, &config).unwrap();
+        assert!((katz[&1] - 1.0).abs() < 1e-9, "Katz of 1 is not 1");
+        assert!(
+            (katz[&3] - 1.3).abs() < 1e-9,
+            "Katz of 3 is not 1 + 0.1 * (2 + 1)"
+        );
+        assert!(
+            (katz[&4] - 1.13).abs() < 1e-9,
+            "Katz of 4 is not 1 + 0.1 * 1.3"
+        );
+
+        // Alpha above 1 / largest eigenvalue never settles
+        let mut cycle = Graph::<u32>::new(true);
+        let node_ptr1 = cycle.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = cycle.add_node(Node::new(2)).expect("Failed to add node");
+        cycle.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        cycle.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        config.set_alpha(2.0);
+        assert!(
+            katz_centrality(&cycle, &config).is_err(),
+            "Divergent Katz centrality converged"
+        );
+
+        let empty = Graph::<u32>::new(true);
+        assert!(
+            matches!(
+                katz_centrality(&empty, &config),
+                Err(AlgorithmError::InvalidGraph(_))
+            ),
+            "Empty graph was not rejected as invalid"
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::alg::alg::{degeneracy_order, AlgorithmError};
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// Iterator over the maximal cliques of an undirected graph, created by maximal_cliques()
+///
+/// Uses Bron-Kerbosch with pivoting, with the outer level visiting nodes in degeneracy
+/// order to keep the search shallow on sparse graphs. Cliques are found lazily
+pub struct MaximalCliques<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    neighbours: Vec<HashSet<usize>>,
+    order: Vec<usize>,
+    position: Vec<usize>,
+    next_root: usize,
+    stack: Vec<Frame>,
+}
+
+/// One level of the Bron-Kerbosch recursion
+struct Frame {
+    clique: Vec<usize>,
+    candidates: Vec<usize>,
+    excluded: Vec<usize>,
+    todo: Vec<usize>,
+}
+
+/// Returns an iterator over every maximal clique of an undirected graph
+///
+/// A clique is maximal if no other node is adjacent to all of its members. Self loops
+/// are ignored
+pub fn maximal_cliques<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<MaximalCliques<T>, AlgorithmError> {
+    let neighbours: Vec<HashSet<usize>> = graph.get_undirected_neighbours()?;
+
+    let order = degeneracy_order(&neighbours);
+    let mut position = vec![0; order.len()];
+    for (p, &v) in order.iter().enumerate() {
+        position[v] = p;
+    }
+
+    Ok(MaximalCliques {
+        nodes: graph
+            .get_nodes()
+            .iter()
+            .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect(),
+        neighbours,
+        order,
+        position,
+        next_root: 0,
+        stack: Vec::new(),
+    })
+}
+
+/// Returns a clique with the most nodes in an undirected graph
+///
+/// Empty if the graph has no nodes. This enumerates every maximal clique, so it is
+/// exponential in the worst case
+pub fn maximum_clique<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
+    let mut best = Vec::new();
+    for clique in maximal_cliques(graph)? {
+        if clique.len() > best.len() {
+            best = clique;
+        }
+    }
+
+    Ok(best)
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> MaximalCliques<T> {
+    /// Builds the next frame below clique, or returns the clique if it is maximal
+    fn expand(
+        &mut self,
+        clique: Vec<usize>,
+        candidates: Vec<usize>,
+        excluded: Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                return Some(clique);
+            }
+            return None;
+        }
+
+        // Pivot on the node covering the most candidates, only its non-neighbours need a branch
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|&&u| {
+                candidates
+                    .iter()
+                    .filter(|c| self.neighbours[u].contains(c))
+                    .count()
+            })
+            .cloned()
+            .unwrap();
+
+        let todo = candidates
+            .iter()
+            .filter(|c| !self.neighbours[pivot].contains(c))
+            .cloned()
+            .collect();
+
+        self.stack.push(Frame {
+            clique,
+            candidates,
+            excluded,
+            todo,
+        });
+
+        None
+    }
+
+    fn to_nodes(&self, clique: Vec<usize>) -> Vec<NodeRcWrapper<T>> {
+        clique
+            .into_iter()
+            .map(|i| NodeRcWrapper(Rc::clone(&self.nodes[i].0)))
+            .collect()
+    }
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> Iterator for MaximalCliques<T> {
+    type Item = Vec<NodeRcWrapper<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let found = match self.stack.last_mut() {
+                Some(frame) => match frame.todo.pop() {
+                    Some(v) => {
+                        let mut clique = frame.clique.clone();
+                        clique.push(v);
+
+                        let neighbours = &self.neighbours[v];
+                        let candidates = frame
+                            .candidates
+                            .iter()
+                            .filter(|c| neighbours.contains(c))
+                            .cloned()
+                            .collect();
+                        let excluded = frame
+                            .excluded
+                            .iter()
+                            .filter(|x| neighbours.contains(x))
+                            .cloned()
+                            .collect();
+
+                        // v has been fully explored from this level
+                        frame.candidates.retain(|&c| c != v);
+                        frame.excluded.push(v);
+
+                        self.expand(clique, candidates, excluded)
+                    }
+                    None => {
+                        self.stack.pop();
+                        None
+                    }
+                },
+                None => {
+                    if self.next_root == self.order.len() {
+                        return None;
+                    }
+
+                    // Start from the next node, later nodes may still join, earlier ones may not
+                    let v = self.order[self.next_root];
+                    self.next_root += 1;
+
+                    let (candidates, excluded): (Vec<usize>, Vec<usize>) = self.neighbours[v]
+                        .iter()
+                        .partition(|&&u| self.position[u] > self.position[v]);
+
+                    self.expand(vec![v], candidates, excluded)
+                }
+            };
+
+            if let Some(clique) = found {
+                return Some(self.to_nodes(clique));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{maximal_cliques, maximum_clique};
+
+    #[test]
+    fn cliques() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+        let node_ptr6 = graph.add_node(Node::new(6)).expect("Failed to add node");
+
+        // Complete graph on 1, 2, 3, 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        // Triangle 4, 5, 6 sharing node 4
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 1);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr6.0.as_ref(), 1);
+        graph.add_edge(node_ptr6.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let mut cliques: Vec<Vec<u32>> = maximal_cliques(&graph)
+            .unwrap()
+            .map(|c| {
+                let mut values: Vec<u32> = c.iter().map(|n| *n.0.borrow().get_value()).collect();
+                values.sort();
+                values
+            })
+            .collect();
+        cliques.sort();
+
+        assert_eq!(
+            cliques,
+            vec![vec![1, 2, 3, 4], vec![4, 5, 6]],
+            "Maximal cliques incorrect"
+        );
+
+        let maximum = maximum_clique(&graph).unwrap();
+        assert_eq!(maximum.len(), 4, "Maximum clique is not of size 4");
+    }
+
+    #[test]
+    fn isolated_nodes_and_edges() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 - 2, 3 is isolated
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        let cliques: Vec<usize> = maximal_cliques(&graph).unwrap().map(|c| c.len()).collect();
+        assert_eq!(cliques.len(), 2, "Expected 2 maximal cliques");
+        assert!(cliques.contains(&1), "Isolated node is not a clique");
+        assert!(cliques.contains(&2), "Edge is not a clique");
+
+        assert!(
+            maximal_cliques(&Graph::<u32>::new(true)).is_err(),
+            "Directed graph was accepted"
+        );
+    }
+}
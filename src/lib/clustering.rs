@@ -0,0 +1,241 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Number of triangles every node of an undirected graph belongs to, keyed by node value
+pub fn triangles<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<HashMap<T, usize>, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+
+    Ok(graph.key_by_value(count_triangles(&neighbours)))
+}
+
+/// Local clustering coefficient of every node of an undirected graph, keyed by node value
+///
+/// The fraction of pairs of a node's neighbours that are themselves connected. Nodes with
+/// fewer than two neighbours have a coefficient of 0
+pub fn local_clustering<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+
+    Ok(graph.key_by_value(local_coefficients(&neighbours)))
+}
+
+/// Mean of the local clustering coefficients of an undirected graph, 0 if it has no nodes
+pub fn average_clustering<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<f64, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+    if neighbours.is_empty() {
+        return Ok(0.0);
+    }
+
+    let coefficients = local_coefficients(&neighbours);
+
+    Ok(coefficients.iter().sum::<f64>() / coefficients.len() as f64)
+}
+
+/// Global clustering coefficient (transitivity) of an undirected graph
+///
+/// Three times the number of triangles divided by the number of connected triples,
+/// 0 if there are no connected triples
+pub fn global_clustering<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<f64, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+
+    // Every triangle is counted once at each of its corners
+    let closed: usize = count_triangles(&neighbours).iter().sum();
+    let triples: usize = neighbours
+        .iter()
+        .map(|s| s.len() * s.len().saturating_sub(1) / 2)
+        .sum();
+
+    if triples == 0 {
+        return Ok(0.0);
+    }
+
+    Ok(closed as f64 / triples as f64)
+}
+
+/// Core number of every node of an undirected graph, keyed by node value
+///
+/// The core number is the largest k such that the node belongs to the k-core, the
+/// largest subgraph in which every node has at least k neighbours
+pub fn core_numbers<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<HashMap<T, usize>, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+
+    Ok(graph.key_by_value(compute_core_numbers(&neighbours)))
+}
+
+/// Nodes of the k-core of an undirected graph
+pub fn k_core<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    k: usize,
+) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
+    let neighbours = graph.get_undirected_neighbours()?;
+    let cores = compute_core_numbers(&neighbours);
+
+    Ok(graph
+        .get_nodes()
+        .iter()
+        .zip(cores)
+        .filter(|(_, c)| *c >= k)
+        .map(|(n, _)| NodeRcWrapper(Rc::clone(&n.0)))
+        .collect())
+}
+
+fn count_triangles(neighbours: &[HashSet<usize>]) -> Vec<usize> {
+    let mut counts = vec![0; neighbours.len()];
+
+    for (i, count) in counts.iter_mut().enumerate() {
+        for &j in &neighbours[i] {
+            for &k in &neighbours[i] {
+                if j < k && neighbours[j].contains(&k) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    counts
+}
+
+fn local_coefficients(neighbours: &[HashSet<usize>]) -> Vec<f64> {
+    count_triangles(neighbours)
+        .into_iter()
+        .zip(neighbours)
+        .map(|(t, s)| {
+            let d = s.len();
+            if d < 2 {
+                0.0
+            } else {
+                2.0 * t as f64 / (d * (d - 1)) as f64
+            }
+        })
+        .collect()
+}
+
+/// Batagelj-Zaversnik: repeatedly peel off the node of smallest remaining degree
+fn compute_core_numbers(neighbours: &[HashSet<usize>]) -> Vec<usize> {
+    let n = neighbours.len();
+    let mut degree: Vec<usize> = neighbours.iter().map(|s| s.len()).collect();
+    let max_degree = degree.iter().cloned().max().unwrap_or(0);
+
+    // Nodes bucketed by current degree
+    let mut buckets = vec![HashSet::new(); max_degree + 1];
+    for (i, &d) in degree.iter().enumerate() {
+        buckets[d].insert(i);
+    }
+
+    let mut core = vec![0; n];
+    let mut removed = vec![false; n];
+    let mut current = 0;
+
+    for _ in 0..n {
+        while buckets[current].is_empty() {
+            current += 1;
+        }
+
+        let v = *buckets[current].iter().next().unwrap();
+        buckets[current].remove(&v);
+        removed[v] = true;
+        core[v] = current;
+
+        // Neighbours never drop below the current level, so current only increases
+        for &u in &neighbours[v] {
+            if !removed[u] && degree[u] > current {
+                buckets[degree[u]].remove(&u);
+                degree[u] -= 1;
+                buckets[degree[u]].insert(u);
+            }
+        }
+    }
+
+    core
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{
+        average_clustering, core_numbers, global_clustering, k_core, local_clustering, triangles,
+    };
+
+    #[test]
+    fn triangle_with_tail() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // Triangle 1 - 2 - 3 with a tail 3 - 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let counts = triangles(&graph).unwrap();
+        assert_eq!(counts[&1], 1, "1 is not in 1 triangle");
+        assert_eq!(counts[&3], 1, "3 is not in 1 triangle");
+        assert_eq!(counts[&4], 0, "4 is in a triangle");
+
+        // 3 has neighbours 1, 2, 4 and only 1 - 2 is connected
+        let local = local_clustering(&graph).unwrap();
+        assert_eq!(local[&1], 1.0, "Clustering of 1 is not 1");
+        assert!(
+            (local[&3] - 1.0 / 3.0).abs() < 1e-9,
+            "Clustering of 3 is not 1/3"
+        );
+        assert_eq!(local[&4], 0.0, "Clustering of 4 is not 0");
+
+        let average = average_clustering(&graph).unwrap();
+        assert!(
+            (average - (1.0 + 1.0 + 1.0 / 3.0) / 4.0).abs() < 1e-9,
+            "Average clustering incorrect"
+        );
+
+        // 3 closed triples out of 1 + 1 + 3 connected triples
+        let global = global_clustering(&graph).unwrap();
+        assert!((global - 0.6).abs() < 1e-9, "Transitivity is not 0.6");
+    }
+
+    #[test]
+    fn cores() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+        graph.add_node(Node::new(6)).expect("Failed to add node");
+
+        // Complete graph on 1, 2, 3, 4 with 5 hanging off 4, 6 isolated
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 1);
+
+        let cores = core_numbers(&graph).unwrap();
+        assert_eq!(cores[&1], 3, "Core number of 1 is not 3");
+        assert_eq!(cores[&4], 3, "Core number of 4 is not 3");
+        assert_eq!(cores[&5], 1, "Core number of 5 is not 1");
+        assert_eq!(cores[&6], 0, "Core number of 6 is not 0");
+
+        assert_eq!(k_core(&graph, 3).unwrap().len(), 4, "3-core is not 4 nodes");
+        assert_eq!(k_core(&graph, 1).unwrap().len(), 5, "1-core is not 5 nodes");
+    }
+}
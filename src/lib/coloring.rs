@@ -0,0 +1,256 @@
+use crate::alg::alg::{degeneracy_order, AlgorithmError};
+use crate::graph::Graph;
+
+use std::collections::HashMap;
+
+/// Order in which the greedy coloring visits the nodes
+pub enum ColoringStrategy {
+    /// Nodes are colored in order of decreasing degree
+    LargestFirst,
+    /// Nodes are colored in the reverse of the order they are removed when
+    /// repeatedly removing the node of smallest remaining degree
+    SmallestLast,
+}
+
+/// Result of a vertex coloring, keyed by node value
+///
+/// Colors are numbered from 0, so every color is less than get_num_colors()
+pub struct Coloring<T: std::cmp::Eq + std::hash::Hash + Clone> {
+    colors: HashMap<T, usize>,
+    num_colors: usize,
+    lower_bound: usize,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash + Clone> Coloring<T> {
+    pub fn get_colors(&self) -> &HashMap<T, usize> {
+        &self.colors
+    }
+
+    pub fn get_num_colors(&self) -> usize {
+        self.num_colors
+    }
+
+    /// Size of the largest clique found, no coloring can use fewer colors than this
+    pub fn get_lower_bound(&self) -> usize {
+        self.lower_bound
+    }
+}
+
+/// Colors an undirected graph greedily, visiting the nodes in the order given by strategy
+///
+/// Each node receives the smallest color not already used by one of its neighbours
+pub fn greedy_coloring<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    strategy: ColoringStrategy,
+) -> Result<Coloring<T>, AlgorithmError> {
+    let adjacency: Vec<Vec<usize>> = graph.get_undirected_neighbours()?;
+
+    let order = match strategy {
+        ColoringStrategy::LargestFirst => largest_first_order(&adjacency),
+        ColoringStrategy::SmallestLast => smallest_last_order(&adjacency),
+    };
+
+    let mut colors = vec![usize::MAX; adjacency.len()];
+    for i in order {
+        colors[i] = smallest_free_color(&adjacency[i], &colors);
+    }
+
+    Ok(build_coloring(graph, &adjacency, colors))
+}
+
+/// Colors an undirected graph with the DSatur heuristic
+///
+/// The next node colored is always the one with the most distinct colors among its
+/// neighbours (its saturation), ties are broken by degree
+pub fn dsatur_coloring<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Coloring<T>, AlgorithmError> {
+    let adjacency: Vec<Vec<usize>> = graph.get_undirected_neighbours()?;
+    let n = adjacency.len();
+
+    let mut colors = vec![usize::MAX; n];
+    let mut neighbour_colors = vec![Vec::<usize>::new(); n];
+
+    for _ in 0..n {
+        // Pick uncolored node with highest saturation, then highest degree
+        let next = (0..n)
+            .filter(|&i| colors[i] == usize::MAX)
+            .max_by(|&a, &b| {
+                (neighbour_colors[a].len(), adjacency[a].len())
+                    .cmp(&(neighbour_colors[b].len(), adjacency[b].len()))
+                    .then(b.cmp(&a))
+            })
+            .unwrap();
+
+        let color = smallest_free_color(&adjacency[next], &colors);
+        colors[next] = color;
+
+        // Update saturation of neighbours
+        for &m in &adjacency[next] {
+            if !neighbour_colors[m].contains(&color) {
+                neighbour_colors[m].push(color);
+            }
+        }
+    }
+
+    Ok(build_coloring(graph, &adjacency, colors))
+}
+
+fn largest_first_order(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..adjacency.len()).collect();
+    order.sort_by(|&a, &b| adjacency[b].len().cmp(&adjacency[a].len()));
+    order
+}
+
+fn smallest_last_order(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let mut order = degeneracy_order(adjacency);
+    order.reverse();
+    order
+}
+
+fn smallest_free_color(neighbours: &[usize], colors: &[usize]) -> usize {
+    let mut used: Vec<usize> = neighbours
+        .iter()
+        .map(|&m| colors[m])
+        .filter(|&c| c != usize::MAX)
+        .collect();
+    used.sort_unstable();
+    used.dedup();
+
+    // First gap in the sorted list of used colors
+    used.iter()
+        .enumerate()
+        .find(|(i, &c)| *i != c)
+        .map(|(i, _)| i)
+        .unwrap_or(used.len())
+}
+
+/// Size of a clique found by greedily growing one from every node
+fn clique_lower_bound(adjacency: &[Vec<usize>]) -> usize {
+    let mut best = 0;
+
+    for start in 0..adjacency.len() {
+        let mut candidates = adjacency[start].clone();
+        candidates.sort_by(|&a, &b| adjacency[b].len().cmp(&adjacency[a].len()));
+
+        let mut clique = vec![start];
+        for c in candidates {
+            if clique.iter().all(|&m| adjacency[c].contains(&m)) {
+                clique.push(c);
+            }
+        }
+
+        best = best.max(clique.len());
+    }
+
+    best
+}
+
+fn build_coloring<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    adjacency: &[Vec<usize>],
+    colors: Vec<usize>,
+) -> Coloring<T> {
+    let num_colors = colors.iter().map(|&c| c + 1).max().unwrap_or(0);
+
+    Coloring {
+        colors: graph.key_by_value(colors),
+        num_colors,
+        lower_bound: clique_lower_bound(adjacency),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{dsatur_coloring, greedy_coloring, ColoringStrategy};
+
+    #[test]
+    fn odd_cycle() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+
+        // 1 - 2 - 3 - 4 - 5 - 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 1);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr1.0.as_ref(), 1);
+
+        let colorings = vec![
+            greedy_coloring(&graph, ColoringStrategy::LargestFirst).unwrap(),
+            greedy_coloring(&graph, ColoringStrategy::SmallestLast).unwrap(),
+            dsatur_coloring(&graph).unwrap(),
+        ];
+
+        for coloring in colorings {
+            // Odd cycle needs exactly 3 colors, largest clique is an edge
+            assert_eq!(
+                coloring.get_num_colors(),
+                3,
+                "Coloring does not use 3 colors"
+            );
+            assert_eq!(coloring.get_lower_bound(), 2, "Lower bound is not 2");
+
+            // No edge may join two nodes of the same color
+            for n in graph.get_nodes() {
+                let color = coloring.get_colors()[n.0.borrow().get_value()];
+                for e in n.0.borrow().get_edges() {
+                    assert_ne!(
+                        color,
+                        coloring.get_colors()[e.get_node().0.borrow().get_value()],
+                        "Neighbours share a color"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wheel() {
+        let mut graph = Graph::<u32>::new(false);
+
+        // Hub node 0 connected to a 4-cycle 1 - 2 - 3 - 4 - 1
+        let hub = graph.add_node(Node::new(0)).expect("Failed to add node");
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(hub.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(hub.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(hub.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(hub.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let coloring = dsatur_coloring(&graph).unwrap();
+        assert_eq!(coloring.get_num_colors(), 3, "DSatur does not use 3 colors");
+        assert_eq!(coloring.get_lower_bound(), 3, "Lower bound is not 3");
+
+        // Opposite rim nodes can share a color, the hub cannot share with anyone
+        let colors = coloring.get_colors();
+        assert_eq!(colors[&1], colors[&3], "Rim colored poorly");
+        assert_eq!(colors[&2], colors[&4], "Rim colored poorly");
+        assert_ne!(colors[&0], colors[&1], "Hub shares a color");
+        assert_ne!(colors[&0], colors[&2], "Hub shares a color");
+    }
+
+    #[test]
+    fn directed_rejected() {
+        let graph = Graph::<u32>::new(true);
+
+        assert!(
+            dsatur_coloring(&graph).is_err(),
+            "Directed graph was colored"
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::Graph;
+
+use std::collections::HashMap;
+
+/// Community assignment of every node, keyed by node value
+///
+/// Communities are numbered from 0, so every community is less than get_num_communities()
+pub struct Communities<T: std::cmp::Eq + std::hash::Hash> {
+    communities: HashMap<T, usize>,
+    num_communities: usize,
+    modularity: f64,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> Communities<T> {
+    pub fn get_communities(&self) -> &HashMap<T, usize> {
+        &self.communities
+    }
+
+    pub fn get_num_communities(&self) -> usize {
+        self.num_communities
+    }
+
+    /// Modularity of the final assignment, see modularity()
+    pub fn get_modularity(&self) -> f64 {
+        self.modularity
+    }
+}
+
+/// Detects communities in an undirected weighted graph with the Louvain method
+///
+/// Nodes are repeatedly moved to the neighbouring community that increases modularity
+/// the most, then each community is merged into a single node and the process repeats
+/// until no move improves modularity. Nodes are visited in the order of get_nodes(),
+/// so the result is deterministic
+pub fn louvain<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Communities<T>, AlgorithmError> {
+    let adjacency = weighted_adjacency(graph)?;
+
+    // Community of every original node
+    let mut assignment: Vec<usize> = (0..adjacency.len()).collect();
+
+    let mut level = adjacency.clone();
+    loop {
+        let (moved, communities) = louvain_pass(&level);
+        if !moved {
+            break;
+        }
+
+        // Follow each original node into the community its merged node joined
+        let (labels, count) = relabel(&communities);
+        for a in assignment.iter_mut() {
+            *a = labels[*a];
+        }
+
+        level = aggregate(&level, &labels, count);
+    }
+
+    Ok(build_communities(graph, &adjacency, assignment))
+}
+
+/// Detects communities in an undirected weighted graph with asynchronous label propagation
+///
+/// Every node starts with its own label and repeatedly adopts the label carrying the most
+/// edge weight among its neighbours, updating in place, until no label changes or
+/// max_iterations passes have been made. Ties keep the current label if possible, otherwise
+/// the smallest label is taken, so the result is deterministic
+pub fn label_propagation<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    max_iterations: usize,
+) -> Result<Communities<T>, AlgorithmError> {
+    let adjacency = weighted_adjacency(graph)?;
+    let n = adjacency.len();
+
+    let mut labels: Vec<usize> = (0..n).collect();
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+
+        for i in 0..n {
+            let mut weights = HashMap::<usize, f64>::new();
+            for &(j, w) in &adjacency[i] {
+                if j != i {
+                    *weights.entry(labels[j]).or_insert(0.0) += w;
+                }
+            }
+
+            let best_weight = match weights.values().cloned().reduce(f64::max) {
+                Some(w) => w,
+                None => continue,
+            };
+
+            if weights.get(&labels[i]) == Some(&best_weight) {
+                continue;
+            }
+
+            let best_label = weights
+                .iter()
+                .filter(|(_, &w)| w == best_weight)
+                .map(|(&l, _)| l)
+                .min()
+                .unwrap();
+
+            labels[i] = best_label;
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let (labels, _) = relabel(&labels);
+
+    Ok(build_communities(graph, &adjacency, labels))
+}
+
+/// Modularity of an assignment of nodes to communities in an undirected weighted graph
+///
+/// Modularity compares the weight of edges inside communities with the weight expected
+/// if edges were placed at random keeping every node's degree, ranging from -0.5 to 1.
+/// Nodes missing from communities are treated as being alone in their own community
+pub fn modularity<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    communities: &HashMap<T, usize>,
+) -> Result<f64, AlgorithmError> {
+    let adjacency = weighted_adjacency(graph)?;
+
+    // Give unassigned nodes labels past any used by the caller
+    let mut next = communities.values().max().map_or(0, |&c| c + 1);
+    let assignment: Vec<usize> = graph
+        .get_nodes()
+        .iter()
+        .map(|n| match communities.get(n.0.borrow().get_value()) {
+            Some(&c) => c,
+            None => {
+                next += 1;
+                next - 1
+            }
+        })
+        .collect();
+
+    Ok(compute_modularity(&adjacency, &assignment))
+}
+
+/// One level of Louvain: moves single nodes between communities until nothing improves
+///
+/// Returns whether any node moved and the community of every node
+fn louvain_pass(adjacency: &[Vec<(usize, f64)>]) -> (bool, Vec<usize>) {
+    let n = adjacency.len();
+
+    let degrees: Vec<f64> = adjacency
+        .iter()
+        .map(|edges| edges.iter().map(|&(_, w)| w).sum())
+        .collect();
+    let total: f64 = degrees.iter().sum();
+
+    let mut communities: Vec<usize> = (0..n).collect();
+    let mut community_degree = degrees.clone();
+
+    if total == 0.0 {
+        return (false, communities);
+    }
+
+    let mut moved = false;
+    loop {
+        let mut improved = false;
+
+        for i in 0..n {
+            // Weight from i into each neighbouring community
+            let mut links = HashMap::<usize, f64>::new();
+            for &(j, w) in &adjacency[i] {
+                if j != i {
+                    *links.entry(communities[j]).or_insert(0.0) += w;
+                }
+            }
+
+            // Take i out of its community
+            let current = communities[i];
+            community_degree[current] -= degrees[i];
+
+            let gain = |c: usize, link: f64| link - community_degree[c] * degrees[i] / total;
+
+            // Only leave the current community for a strictly better one
+            let mut candidates: Vec<(usize, f64)> = links.into_iter().collect();
+            candidates.sort_by_key(|&(c, _)| c);
+
+            let mut best = current;
+            let mut best_gain = candidates
+                .iter()
+                .find(|&&(c, _)| c == current)
+                .map_or(gain(current, 0.0), |&(c, link)| gain(c, link));
+            for (c, link) in candidates {
+                let g = gain(c, link);
+                if g > best_gain + 1e-12 {
+                    best = c;
+                    best_gain = g;
+                }
+            }
+
+            community_degree[best] += degrees[i];
+            communities[i] = best;
+
+            if best != current {
+                improved = true;
+                moved = true;
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    (moved, communities)
+}
+
+/// Merges every community into a single node, keeping the edge weight between communities
+/// and turning edges inside a community into a self loop
+fn aggregate(
+    adjacency: &[Vec<(usize, f64)>],
+    labels: &[usize],
+    count: usize,
+) -> Vec<Vec<(usize, f64)>> {
+    let mut merged = vec![HashMap::<usize, f64>::new(); count];
+    for (i, edges) in adjacency.iter().enumerate() {
+        for &(j, w) in edges {
+            *merged[labels[i]].entry(labels[j]).or_insert(0.0) += w;
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|edges| {
+            let mut edges: Vec<(usize, f64)> = edges.into_iter().collect();
+            edges.sort_by_key(|&(j, _)| j);
+            edges
+        })
+        .collect()
+}
+
+/// Renumbers labels to 0..count in order of first appearance
+fn relabel(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut mapping = HashMap::<usize, usize>::new();
+    let relabelled = labels
+        .iter()
+        .map(|l| {
+            let next = mapping.len();
+            *mapping.entry(*l).or_insert(next)
+        })
+        .collect();
+
+    (relabelled, mapping.len())
+}
+
+fn compute_modularity(adjacency: &[Vec<(usize, f64)>], assignment: &[usize]) -> f64 {
+    let mut inside = HashMap::<usize, f64>::new();
+    let mut degree = HashMap::<usize, f64>::new();
+    let mut total = 0.0;
+
+    for (i, edges) in adjacency.iter().enumerate() {
+        for &(j, w) in edges {
+            total += w;
+            *degree.entry(assignment[i]).or_insert(0.0) += w;
+            if assignment[i] == assignment[j] {
+                *inside.entry(assignment[i]).or_insert(0.0) += w;
+            }
+        }
+    }
+
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    degree
+        .iter()
+        .map(|(c, d)| inside.get(c).cloned().unwrap_or(0.0) / total - (d / total).powi(2))
+        .sum()
+}
+
+/// Returns the adjacency list with weights as f64, rejecting directed graphs
+fn weighted_adjacency<T: std::cmp::Eq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<Vec<Vec<(usize, f64)>>, AlgorithmError> {
+    if graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be undirected".to_string(),
+        ));
+    }
+
+    Ok(graph
+        .get_adjacency()
+        .into_iter()
+        .map(|edges| edges.into_iter().map(|(j, w)| (j, w as f64)).collect())
+        .collect())
+}
+
+fn build_communities<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    adjacency: &[Vec<(usize, f64)>],
+    assignment: Vec<usize>,
+) -> Communities<T> {
+    let modularity = compute_modularity(adjacency, &assignment);
+    let num_communities = assignment.iter().map(|&c| c + 1).max().unwrap_or(0);
+
+    let communities = graph.key_by_value(assignment);
+
+    Communities {
+        communities,
+        num_communities,
+        modularity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{label_propagation, louvain, modularity};
+
+    /// Two triangles 1-2-3 and 4-5-6 joined by a light 3 - 4 edge
+    fn two_triangles() -> Graph<u32> {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+        let node_ptr6 = graph.add_node(Node::new(6)).expect("Failed to add node");
+
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 5);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 5);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 5);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 5);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr6.0.as_ref(), 5);
+        graph.add_edge(node_ptr6.0.as_ref(), node_ptr4.0.as_ref(), 5);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        graph
+    }
+
+    #[test]
+    fn louvain_two_triangles() {
+        let graph = two_triangles();
+
+        let result = louvain(&graph).unwrap();
+        let communities = result.get_communities();
+
+        assert_eq!(result.get_num_communities(), 2, "Expected 2 communities");
+        assert_eq!(communities[&1], communities[&2], "1 and 2 split");
+        assert_eq!(communities[&1], communities[&3], "1 and 3 split");
+        assert_eq!(communities[&4], communities[&5], "4 and 5 split");
+        assert_eq!(communities[&4], communities[&6], "4 and 6 split");
+        assert_ne!(communities[&1], communities[&4], "Triangles merged");
+
+        let expected = modularity(&graph, communities).unwrap();
+        assert!(
+            (result.get_modularity() - expected).abs() < 1e-9,
+            "Reported modularity is wrong"
+        );
+        assert!(result.get_modularity() > 0.4, "Modularity is too low");
+    }
+
+    #[test]
+    fn label_propagation_two_triangles() {
+        let graph = two_triangles();
+
+        let result = label_propagation(&graph, 100).unwrap();
+        let communities = result.get_communities();
+
+        assert_eq!(result.get_num_communities(), 2, "Expected 2 communities");
+        assert_eq!(communities[&1], communities[&3], "1 and 3 split");
+        assert_eq!(communities[&4], communities[&6], "4 and 6 split");
+        assert_ne!(communities[&1], communities[&4], "Triangles merged");
+    }
+
+    #[test]
+    fn directed_rejected() {
+        let graph = Graph::<u32>::new(true);
+
+        assert!(louvain(&graph).is_err(), "Directed graph was accepted");
+    }
+}
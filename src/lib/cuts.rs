@@ -0,0 +1,415 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+/// Minimum cut of an undirected graph, created by minimum_cut()
+pub struct MinCut<T: std::cmp::PartialEq + std::hash::Hash> {
+    value: u32,
+    first: Vec<NodeRcWrapper<T>>,
+    second: Vec<NodeRcWrapper<T>>,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> MinCut<T> {
+    /// Total weight of the edges between the two sides
+    pub fn get_value(&self) -> u32 {
+        self.value
+    }
+
+    /// Both sides of the cut, every node is on exactly one
+    pub fn get_partitions(&self) -> (&Vec<NodeRcWrapper<T>>, &Vec<NodeRcWrapper<T>>) {
+        (&self.first, &self.second)
+    }
+}
+
+/// Gomory-Hu tree of an undirected graph, created by gomory_hu_tree()
+///
+/// The minimum cut between any two nodes of the graph equals the lightest edge on the
+/// path between them in the tree
+pub struct GomoryHuTree<T: std::cmp::Eq + std::hash::Hash> {
+    tree: Graph<T>,
+    indices: HashMap<T, usize>,
+    parent: Vec<usize>,
+    weights: Vec<u32>,
+    depth: Vec<usize>,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> GomoryHuTree<T> {
+    /// Tree over the same nodes, each edge weighted with the cut it stands for
+    pub fn get_tree(&self) -> &Graph<T> {
+        &self.tree
+    }
+
+    /// Value of the minimum cut separating a and b
+    ///
+    /// Returns None if either value is not a node or both are the same node
+    pub fn min_cut_value(&self, a: &T, b: &T) -> Option<u32> {
+        let (mut a, mut b) = (*self.indices.get(a)?, *self.indices.get(b)?);
+        if a == b {
+            return None;
+        }
+
+        let mut lightest = u32::MAX;
+        while a != b {
+            let deeper = if self.depth[a] >= self.depth[b] {
+                &mut a
+            } else {
+                &mut b
+            };
+
+            lightest = lightest.min(self.weights[*deeper]);
+            *deeper = self.parent[*deeper];
+        }
+
+        Some(lightest)
+    }
+}
+
+/// Finds the minimum weight set of edges whose removal disconnects an undirected graph
+///
+/// Uses Stoer-Wagner. A graph that is already disconnected has a cut of 0, a cut larger
+/// than u32::MAX is an error
+pub fn minimum_cut<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<MinCut<T>, AlgorithmError> {
+    let mut weights = weight_matrix(graph)?;
+    let n = weights.len();
+    if n < 2 {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must have at least 2 nodes".to_string(),
+        ));
+    }
+
+    // Original nodes merged into each remaining node
+    let mut groups: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut active = vec![true; n];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    for phase in 0..n - 1 {
+        // Maximum adjacency order, always add the node most tightly connected to the set
+        let mut added = vec![false; n];
+        let mut connection = vec![0; n];
+        let (mut s, mut t) = (0, 0);
+
+        for _ in 0..n - phase {
+            let next = (0..n)
+                .filter(|&v| active[v] && !added[v])
+                .max_by_key(|&v| (connection[v], std::cmp::Reverse(v)))
+                .unwrap();
+
+            added[next] = true;
+            (s, t) = (t, next);
+
+            for v in 0..n {
+                if active[v] && !added[v] {
+                    connection[v] += weights[next][v];
+                }
+            }
+        }
+
+        // The cut of the phase separates the last node added from the rest
+        if best
+            .as_ref()
+            .is_none_or(|(value, _)| connection[t] < *value)
+        {
+            best = Some((connection[t], groups[t].clone()));
+        }
+
+        // Merge t into s
+        let merged = std::mem::take(&mut groups[t]);
+        groups[s].extend(merged);
+        active[t] = false;
+        let row = weights[t].clone();
+        for (v, weight) in row.into_iter().enumerate() {
+            weights[s][v] += weight;
+            weights[v][s] = weights[s][v];
+        }
+        weights[s][s] = 0;
+    }
+
+    let (value, side) = best.unwrap();
+    let value = u32::try_from(value)
+        .map_err(|_| AlgorithmError::InvalidGraph("Minimum cut exceeds u32::MAX".to_string()))?;
+    let mut in_first = vec![false; n];
+    for i in side {
+        in_first[i] = true;
+    }
+
+    let (first, second) = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (i, NodeRcWrapper(Rc::clone(&node.0))))
+        .partition::<Vec<_>, _>(|(i, _)| in_first[*i]);
+
+    Ok(MinCut {
+        value,
+        first: first.into_iter().map(|(_, n)| n).collect(),
+        second: second.into_iter().map(|(_, n)| n).collect(),
+    })
+}
+
+/// Builds the Gomory-Hu tree of an undirected graph with Gusfield's algorithm
+///
+/// Needs one maximum flow computation per node, after which any pairwise minimum cut
+/// value is a walk up the tree. Cuts larger than u32::MAX are an error
+pub fn gomory_hu_tree<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<GomoryHuTree<T>, AlgorithmError> {
+    let capacity = weight_matrix(graph)?;
+    let n = capacity.len();
+    if n == 0 {
+        return Err(AlgorithmError::InvalidGraph(
+            "No nodes exist in graph".to_string(),
+        ));
+    }
+
+    // Every node starts attached to node 0, which stays the root
+    let mut parent = vec![0; n];
+    let mut weights = vec![0; n];
+
+    for s in 1..n {
+        let t = parent[s];
+        let (value, source_side) = max_flow(&capacity, s, t);
+        weights[s] = value;
+
+        for i in 0..n {
+            if i != s && source_side[i] && parent[i] == t {
+                parent[i] = s;
+            }
+        }
+
+        // Keep the tree a cut tree when the parent of t falls on the side of s
+        if source_side[parent[t]] {
+            parent[s] = parent[t];
+            parent[t] = s;
+            weights[s] = weights[t];
+            weights[t] = value;
+        }
+    }
+
+    let weights = weights
+        .into_iter()
+        .map(u32::try_from)
+        .collect::<Result<Vec<u32>, _>>()
+        .map_err(|_| AlgorithmError::InvalidGraph("Minimum cut exceeds u32::MAX".to_string()))?;
+
+    let mut depth = vec![None; n];
+    depth[0] = Some(0);
+    for i in 0..n {
+        let mut chain = Vec::new();
+        let mut current = i;
+        while depth[current].is_none() {
+            chain.push(current);
+            current = parent[current];
+        }
+
+        let mut d = depth[current].unwrap();
+        for &node in chain.iter().rev() {
+            d += 1;
+            depth[node] = Some(d);
+        }
+    }
+
+    let mut tree = Graph::new(false);
+    let nodes: Vec<NodeRcWrapper<T>> = graph
+        .get_nodes()
+        .iter()
+        .map(|n| {
+            tree.add_node(Node::new(n.0.borrow().get_value().clone()))
+                .unwrap()
+        })
+        .collect();
+    for i in 1..n {
+        tree.add_edge(nodes[i].0.as_ref(), nodes[parent[i]].0.as_ref(), weights[i]);
+    }
+
+    Ok(GomoryHuTree {
+        tree,
+        indices: nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.0.borrow().get_value().clone(), i))
+            .collect(),
+        parent,
+        weights,
+        depth: depth.into_iter().map(|d| d.unwrap()).collect(),
+    })
+}
+
+/// Edmonds-Karp maximum flow from source to sink
+///
+/// Returns the flow and which nodes are on the source side of the minimum cut
+fn max_flow(capacity: &[Vec<u64>], source: usize, sink: usize) -> (u64, Vec<bool>) {
+    let n = capacity.len();
+    let mut residual = capacity.to_vec();
+    let mut flow = 0;
+
+    loop {
+        // Shortest augmenting path by breadth first search
+        let mut previous = vec![None; n];
+        let mut seen = vec![false; n];
+        seen[source] = true;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for v in 0..n {
+                if !seen[v] && residual[u][v] > 0 {
+                    seen[v] = true;
+                    previous[v] = Some(u);
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        // Without an augmenting path, the nodes still reachable form the source side
+        if !seen[sink] {
+            return (flow, seen);
+        }
+
+        let mut bottleneck = u64::MAX;
+        let mut v = sink;
+        while let Some(u) = previous[v] {
+            bottleneck = bottleneck.min(residual[u][v]);
+            v = u;
+        }
+
+        let mut v = sink;
+        while let Some(u) = previous[v] {
+            residual[u][v] -= bottleneck;
+            residual[v][u] += bottleneck;
+            v = u;
+        }
+
+        flow += bottleneck;
+    }
+}
+
+/// Edge weights of an undirected graph as a matrix, ignoring self loops
+///
+/// Weights are widened to u64 so sums of edges cannot overflow
+fn weight_matrix<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<Vec<Vec<u64>>, AlgorithmError> {
+    if graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be undirected".to_string(),
+        ));
+    }
+
+    let adjacency = graph.get_adjacency();
+    let mut weights = vec![vec![0; adjacency.len()]; adjacency.len()];
+    for (i, edges) in adjacency.into_iter().enumerate() {
+        for (j, weight) in edges {
+            if i != j {
+                weights[i][j] = weight as u64;
+            }
+        }
+    }
+
+    Ok(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{gomory_hu_tree, minimum_cut};
+
+    /// Two triangles with heavy edges, joined by two light edges
+    fn two_triangles() -> Graph<u32> {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+        let node_ptr6 = graph.add_node(Node::new(6)).expect("Failed to add node");
+
+        // Triangles 1 - 2 - 3 and 4 - 5 - 6
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 3);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 3);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 3);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 3);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr6.0.as_ref(), 3);
+        graph.add_edge(node_ptr6.0.as_ref(), node_ptr4.0.as_ref(), 3);
+
+        // 3 - 4 and 2 - 5 join them
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr5.0.as_ref(), 1);
+
+        graph
+    }
+
+    #[test]
+    fn stoer_wagner() {
+        let graph = two_triangles();
+
+        let cut = minimum_cut(&graph).unwrap();
+        assert_eq!(cut.get_value(), 2, "Minimum cut is not 2");
+
+        let (first, second) = cut.get_partitions();
+        let mut first: Vec<u32> = first.iter().map(|n| *n.0.borrow().get_value()).collect();
+        let mut second: Vec<u32> = second.iter().map(|n| *n.0.borrow().get_value()).collect();
+        first.sort();
+        second.sort();
+
+        let mut sides = vec![first, second];
+        sides.sort();
+        assert_eq!(
+            sides,
+            vec![vec![1, 2, 3], vec![4, 5, 6]],
+            "Cut does not separate the triangles"
+        );
+    }
+
+    #[test]
+    fn gomory_hu() {
+        let graph = two_triangles();
+
+        let tree = gomory_hu_tree(&graph).unwrap();
+        assert_eq!(tree.min_cut_value(&1, &4), Some(2), "Cut 1 / 4 is not 2");
+        assert_eq!(tree.min_cut_value(&3, &6), Some(2), "Cut 3 / 6 is not 2");
+        // 1 is cut off on its own by its two triangle edges
+        assert_eq!(tree.min_cut_value(&1, &2), Some(6), "Cut 1 / 2 is not 6");
+        // 2 and 3 each have 7, the cheapest cut is isolating one of them
+        assert_eq!(tree.min_cut_value(&2, &3), Some(7), "Cut 2 / 3 is not 7");
+        assert_eq!(tree.min_cut_value(&1, &1), None, "Node has a cut to itself");
+
+        let edges: usize = tree
+            .get_tree()
+            .get_nodes()
+            .iter()
+            .map(|n| n.0.borrow().get_edges().len())
+            .sum();
+        assert_eq!(edges / 2, 5, "Tree does not have 5 edges");
+    }
+
+    #[test]
+    fn heavy_edges() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // Triangle 1 - 2 - 3 of u32::MAX edges, 3 - 4 is light
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        // Connections inside the triangle pass u32::MAX but the cut does not
+        let cut = minimum_cut(&graph).unwrap();
+        assert_eq!(cut.get_value(), 1, "Minimum cut is not 1");
+
+        // Cutting 1 from 2 costs twice u32::MAX
+        assert!(
+            gomory_hu_tree(&graph).is_err(),
+            "Cut past u32::MAX was accepted"
+        );
+    }
+}
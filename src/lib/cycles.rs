@@ -0,0 +1,282 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::rc::Rc;
+
+/// Iterator over the simple cycles of a directed graph, created by simple_cycles()
+///
+/// Uses Johnson's algorithm. Cycles are found lazily, each one starting at the node that
+/// comes first in the graph and without repeating it at the end
+pub struct SimpleCycles<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    max_length: Option<usize>,
+    remaining: Option<usize>,
+    next_start: usize,
+    // Search state for the current start node
+    in_component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    stack: Vec<(usize, usize)>,
+    closed: Vec<bool>,
+}
+
+/// Returns an iterator over every simple cycle of a directed graph
+///
+/// A simple cycle visits no node twice. Cycles longer than max_length nodes are skipped
+/// and at most max_cycles are returned. With a length limit Johnson's blocking no longer
+/// holds, so the search falls back to a depth-first search cut off at the limit
+pub fn simple_cycles<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    max_length: Option<usize>,
+    max_cycles: Option<usize>,
+) -> Result<SimpleCycles<T>, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    let successors: Vec<Vec<usize>> = graph
+        .get_adjacency()
+        .into_iter()
+        .map(|edges| edges.into_iter().map(|(j, _)| j).collect())
+        .collect();
+
+    let n = successors.len();
+    let mut predecessors = vec![Vec::new(); n];
+    for (u, edges) in successors.iter().enumerate() {
+        for &v in edges {
+            predecessors[v].push(u);
+        }
+    }
+
+    Ok(SimpleCycles {
+        nodes: graph
+            .get_nodes()
+            .iter()
+            .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect(),
+        successors,
+        predecessors,
+        max_length,
+        remaining: max_cycles,
+        next_start: 0,
+        in_component: vec![false; n],
+        blocked: vec![false; n],
+        blocked_by: vec![Vec::new(); n],
+        stack: Vec::new(),
+        closed: Vec::new(),
+    })
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> SimpleCycles<T> {
+    /// Prepares the search for cycles whose first node is start
+    fn begin(&mut self, start: usize) {
+        // Only the strongly connected component of start among the later nodes matters
+        let forward = self.reach(start, &self.successors);
+        let backward = self.reach(start, &self.predecessors);
+        for (i, in_component) in self.in_component.iter_mut().enumerate() {
+            *in_component = forward[i] && backward[i];
+        }
+
+        self.blocked.iter_mut().for_each(|b| *b = false);
+        self.blocked_by.iter_mut().for_each(|b| b.clear());
+
+        self.blocked[start] = true;
+        self.stack.push((start, 0));
+        self.closed.push(false);
+    }
+
+    /// Nodes no earlier than start that can be reached from start along edges
+    fn reach(&self, start: usize, edges: &[Vec<usize>]) -> Vec<bool> {
+        let mut seen = vec![false; edges.len()];
+        seen[start] = true;
+
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            for &w in &edges[v] {
+                if w >= start && !seen[w] {
+                    seen[w] = true;
+                    stack.push(w);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Advances the search by one edge, returning a cycle if that edge closes one
+    fn step(&mut self) -> Option<Vec<usize>> {
+        let start = self.stack[0].0;
+        let depth = self.stack.len();
+        let (v, next) = self.stack[depth - 1];
+
+        if next < self.successors[v].len() {
+            self.stack[depth - 1].1 += 1;
+            let w = self.successors[v][next];
+
+            if w == start {
+                self.closed[depth - 1] = true;
+                return Some(self.stack.iter().map(|&(u, _)| u).collect());
+            }
+
+            let room = self.max_length.is_none_or(|max| depth < max);
+            if self.in_component[w] && !self.blocked[w] && room {
+                self.blocked[w] = true;
+                self.stack.push((w, 0));
+                self.closed.push(false);
+            }
+
+            return None;
+        }
+
+        // Every edge out of v is done
+        self.stack.pop();
+        let closed = self.closed.pop().unwrap();
+
+        if self.max_length.is_some() {
+            self.blocked[v] = false;
+        } else if closed {
+            self.unblock(v);
+        } else {
+            // v stays blocked until one of its successors is freed
+            for i in 0..self.successors[v].len() {
+                let w = self.successors[v][i];
+                if self.in_component[w] && !self.blocked_by[w].contains(&v) {
+                    self.blocked_by[w].push(v);
+                }
+            }
+        }
+
+        if closed {
+            if let Some(parent) = self.closed.last_mut() {
+                *parent = true;
+            }
+        }
+
+        None
+    }
+
+    fn unblock(&mut self, v: usize) {
+        let mut stack = vec![v];
+        while let Some(u) = stack.pop() {
+            if self.blocked[u] {
+                self.blocked[u] = false;
+                stack.append(&mut self.blocked_by[u]);
+            }
+        }
+    }
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> Iterator for SimpleCycles<T> {
+    type Item = Vec<NodeRcWrapper<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        loop {
+            if self.stack.is_empty() {
+                if self.next_start >= self.nodes.len() {
+                    return None;
+                }
+
+                self.begin(self.next_start);
+                self.next_start += 1;
+            }
+
+            if let Some(cycle) = self.step() {
+                if let Some(remaining) = self.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+
+                return Some(
+                    cycle
+                        .into_iter()
+                        .map(|i| NodeRcWrapper(Rc::clone(&self.nodes[i].0)))
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::simple_cycles;
+
+    #[test]
+    fn small_cycles() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 3 -> 1, 2 -> 1 and 3 -> 4 -> 3
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        let mut cycles: Vec<Vec<u32>> = simple_cycles(&graph, None, None)
+            .unwrap()
+            .map(|c| c.iter().map(|n| *n.0.borrow().get_value()).collect())
+            .collect();
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![vec![1, 2], vec![1, 2, 3], vec![3, 4]],
+            "Cycles incorrect"
+        );
+
+        assert_eq!(
+            simple_cycles(&graph, Some(2), None).unwrap().count(),
+            2,
+            "Cycles of at most 2 nodes are not 1, 2 and 3, 4"
+        );
+        assert_eq!(
+            simple_cycles(&graph, None, Some(1)).unwrap().count(),
+            1,
+            "Cycle count limit ignored"
+        );
+    }
+
+    #[test]
+    fn complete_graph() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let nodes: Vec<_> = (0..4)
+            .map(|i| graph.add_node(Node::new(i)).expect("Failed to add node"))
+            .collect();
+
+        // Every ordered pair of distinct nodes is an edge
+        for from in &nodes {
+            for to in &nodes {
+                if from.0.borrow().get_value() != to.0.borrow().get_value() {
+                    graph.add_edge(from.0.as_ref(), to.0.as_ref(), 1);
+                }
+            }
+        }
+
+        // 6 cycles of 2 nodes, 8 of 3 and 6 of 4
+        assert_eq!(
+            simple_cycles(&graph, None, None).unwrap().count(),
+            20,
+            "Complete graph does not have 20 cycles"
+        );
+        assert_eq!(
+            simple_cycles(&graph, Some(3), None).unwrap().count(),
+            14,
+            "Complete graph does not have 14 cycles of at most 3 nodes"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Dominator information for a directed graph, created by dominators()
+///
+/// A node a dominates b if every path from the entry to b passes through a. Nodes that
+/// cannot be reached from the entry have no dominators and an empty frontier
+pub struct Dominators<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    entry: usize,
+    idom: Vec<Option<usize>>,
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> Dominators<T> {
+    pub fn get_entry(&self) -> NodeRcWrapper<T> {
+        NodeRcWrapper(Rc::clone(&self.nodes[self.entry].0))
+    }
+
+    pub fn is_reachable(&self, node: &RefCell<Node<T>>) -> bool {
+        self.index_of(node).is_some_and(|i| self.idom[i].is_some())
+    }
+
+    /// Closest strict dominator of node, None for the entry and unreachable nodes
+    pub fn get_immediate_dominator(&self, node: &RefCell<Node<T>>) -> Option<NodeRcWrapper<T>> {
+        let i = self.index_of(node)?;
+        if i == self.entry {
+            return None;
+        }
+
+        self.idom[i].map(|d| self.wrap(d))
+    }
+
+    /// Children of node in the dominator tree, the nodes it immediately dominates
+    pub fn get_children(&self, node: &RefCell<Node<T>>) -> Vec<NodeRcWrapper<T>> {
+        match self.index_of(node) {
+            Some(i) => (0..self.nodes.len())
+                .filter(|&c| c != self.entry && self.idom[c] == Some(i))
+                .map(|c| self.wrap(c))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns true if a dominates b, every node dominates itself
+    pub fn dominates(&self, a: &RefCell<Node<T>>, b: &RefCell<Node<T>>) -> bool {
+        let (a, mut b) = match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) if self.idom[b].is_some() => (a, b),
+            _ => return false,
+        };
+
+        // Walk up the dominator tree from b
+        loop {
+            if a == b {
+                return true;
+            }
+            if b == self.entry {
+                return false;
+            }
+            b = self.idom[b].unwrap();
+        }
+    }
+
+    /// Nodes where the dominance of node ends, those with a predecessor dominated by node
+    /// that are not themselves strictly dominated by it
+    pub fn get_dominance_frontier(&self, node: &RefCell<Node<T>>) -> Vec<NodeRcWrapper<T>> {
+        match self.index_of(node) {
+            Some(i) => self.frontiers[i].iter().map(|&f| self.wrap(f)).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn index_of(&self, node: &RefCell<Node<T>>) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|n| *n.0.borrow().get_value() == *node.borrow().get_value())
+    }
+
+    fn wrap(&self, i: usize) -> NodeRcWrapper<T> {
+        NodeRcWrapper(Rc::clone(&self.nodes[i].0))
+    }
+}
+
+/// Computes immediate dominators, the dominator tree and dominance frontiers of a directed
+/// graph from entry using the Lengauer-Tarjan algorithm
+pub fn dominators<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    entry: &RefCell<Node<T>>,
+) -> Result<Dominators<T>, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    let entry = graph.get_node_index(entry).ok_or_else(|| {
+        AlgorithmError::InvalidArgument("Entry node does not exist in graph".to_string())
+    })?;
+
+    let adjacency = graph.get_adjacency();
+    let idom = lengauer_tarjan(&adjacency, entry);
+    let frontiers = dominance_frontiers(&adjacency, &idom, entry);
+
+    Ok(Dominators {
+        nodes: graph
+            .get_nodes()
+            .iter()
+            .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect(),
+        entry,
+        idom,
+        frontiers,
+    })
+}
+
+/// Immediate dominator of every node, the entry is its own dominator and unreachable
+/// nodes have none
+fn lengauer_tarjan(adjacency: &[Vec<(usize, u32)>], entry: usize) -> Vec<Option<usize>> {
+    let n = adjacency.len();
+
+    // Depth first numbering from the entry, everything below works on these numbers
+    let mut number = vec![usize::MAX; n];
+    let mut vertex = Vec::new();
+    let mut parent = Vec::new();
+    let mut stack = vec![(entry, usize::MAX)];
+    while let Some((v, p)) = stack.pop() {
+        if number[v] != usize::MAX {
+            continue;
+        }
+        number[v] = vertex.len();
+        vertex.push(v);
+        parent.push(p);
+
+        for &(w, _) in adjacency[v].iter().rev() {
+            if number[w] == usize::MAX {
+                stack.push((w, number[v]));
+            }
+        }
+    }
+
+    let count = vertex.len();
+    let mut predecessors = vec![Vec::new(); count];
+    for &v in &vertex {
+        for &(w, _) in &adjacency[v] {
+            predecessors[number[w]].push(number[v]);
+        }
+    }
+
+    let mut semi: Vec<usize> = (0..count).collect();
+    let mut idom = vec![0; count];
+    let mut ancestor = vec![usize::MAX; count];
+    let mut label: Vec<usize> = (0..count).collect();
+    let mut bucket = vec![Vec::new(); count];
+
+    for w in (1..count).rev() {
+        for &v in &predecessors[w] {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            if semi[u] < semi[w] {
+                semi[w] = semi[u];
+            }
+        }
+        bucket[semi[w]].push(w);
+
+        // Link w into the forest
+        ancestor[w] = parent[w];
+
+        for v in std::mem::take(&mut bucket[parent[w]]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+        }
+    }
+
+    for w in 1..count {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut result = vec![None; n];
+    for (w, &v) in vertex.iter().enumerate() {
+        result[v] = Some(vertex[idom[w]]);
+    }
+
+    result
+}
+
+/// Node with the smallest semi-dominator on the forest path to v, compressing the path
+fn eval(v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v] == usize::MAX {
+        return v;
+    }
+
+    // Collect the path up to the root of the forest, then compress top down
+    let mut path = Vec::new();
+    let mut u = v;
+    while ancestor[ancestor[u]] != usize::MAX {
+        path.push(u);
+        u = ancestor[u];
+    }
+
+    for &x in path.iter().rev() {
+        let a = ancestor[x];
+        if semi[label[a]] < semi[label[x]] {
+            label[x] = label[a];
+        }
+        ancestor[x] = ancestor[a];
+    }
+
+    label[v]
+}
+
+/// Dominance frontier of every node, computed by walking up from each join point
+fn dominance_frontiers(
+    adjacency: &[Vec<(usize, u32)>],
+    idom: &[Option<usize>],
+    entry: usize,
+) -> Vec<Vec<usize>> {
+    let n = adjacency.len();
+
+    let mut predecessors = vec![Vec::new(); n];
+    for (v, edges) in adjacency.iter().enumerate() {
+        if idom[v].is_none() {
+            continue;
+        }
+        for &(w, _) in edges {
+            predecessors[w].push(v);
+        }
+    }
+
+    let mut frontiers = vec![Vec::new(); n];
+    for b in 0..n {
+        // The entry is also entered from outside the graph, so one edge into it is a join
+        let joins = if b == entry { 1 } else { 2 };
+        if predecessors[b].len() < joins {
+            continue;
+        }
+
+        // Nothing strictly dominates the entry, so walks towards it end after passing it
+        let stop = if b == entry { None } else { idom[b] };
+        for &p in &predecessors[b] {
+            let mut runner = p;
+            while Some(runner) != stop {
+                if !frontiers[runner].contains(&b) {
+                    frontiers[runner].push(b);
+                }
+
+                if runner == entry {
+                    break;
+                }
+                runner = idom[runner].unwrap();
+            }
+        }
+    }
+
+    frontiers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::dominators;
+
+    #[test]
+    fn diamond_with_loop() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        let node_ptr5 = graph.add_node(Node::new(5)).expect("Failed to add node");
+        let node_ptr6 = graph.add_node(Node::new(6)).expect("Failed to add node");
+
+        // 1 -> 2, 2 -> 3, 2 -> 4, 3 -> 5, 4 -> 5, 5 -> 2 (loop back), 5 -> 6
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr5.0.as_ref(), 1);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr5.0.as_ref(), 1);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr5.0.as_ref(), node_ptr6.0.as_ref(), 1);
+
+        let result = dominators(&graph, node_ptr1.0.as_ref()).unwrap();
+
+        let idom = |node: &crate::graph::NodeRcWrapper<u32>| {
+            result
+                .get_immediate_dominator(node.0.as_ref())
+                .map(|d| *d.0.borrow().get_value())
+        };
+        assert_eq!(idom(&node_ptr1), None, "Entry has a dominator");
+        assert_eq!(idom(&node_ptr2), Some(1), "idom(2) is not 1");
+        assert_eq!(idom(&node_ptr3), Some(2), "idom(3) is not 2");
+        assert_eq!(idom(&node_ptr4), Some(2), "idom(4) is not 2");
+        assert_eq!(idom(&node_ptr5), Some(2), "idom(5) is not 2");
+        assert_eq!(idom(&node_ptr6), Some(5), "idom(6) is not 5");
+
+        assert!(
+            result.dominates(node_ptr2.0.as_ref(), node_ptr6.0.as_ref()),
+            "2 does not dominate 6"
+        );
+        assert!(
+            !result.dominates(node_ptr3.0.as_ref(), node_ptr5.0.as_ref()),
+            "3 dominates 5"
+        );
+        assert_eq!(
+            result.get_children(node_ptr2.0.as_ref()).len(),
+            3,
+            "2 does not have 3 children"
+        );
+
+        let frontier = |node: &crate::graph::NodeRcWrapper<u32>| {
+            let mut values: Vec<u32> = result
+                .get_dominance_frontier(node.0.as_ref())
+                .iter()
+                .map(|n| *n.0.borrow().get_value())
+                .collect();
+            values.sort();
+            values
+        };
+        assert_eq!(frontier(&node_ptr3), vec![5], "DF(3) is not {{5}}");
+        assert_eq!(frontier(&node_ptr4), vec![5], "DF(4) is not {{5}}");
+        assert_eq!(frontier(&node_ptr5), vec![2], "DF(5) is not {{2}}");
+        assert_eq!(frontier(&node_ptr2), vec![2], "DF(2) is not {{2}}");
+        assert!(frontier(&node_ptr6).is_empty(), "DF(6) is not empty");
+    }
+
+    #[test]
+    fn loop_to_entry() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 -> 2 -> 1 loops back to the entry, 2 -> 3 leaves the loop
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        let result = dominators(&graph, node_ptr1.0.as_ref()).unwrap();
+
+        let frontier = |node: &crate::graph::NodeRcWrapper<u32>| -> Vec<u32> {
+            result
+                .get_dominance_frontier(node.0.as_ref())
+                .iter()
+                .map(|n| *n.0.borrow().get_value())
+                .collect()
+        };
+        assert_eq!(frontier(&node_ptr2), vec![1], "DF(2) is not {{1}}");
+        assert_eq!(frontier(&node_ptr1), vec![1], "DF(1) is not {{1}}");
+        assert!(frontier(&node_ptr3).is_empty(), "DF(3) is not empty");
+    }
+
+    #[test]
+    fn unreachable_and_invalid() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 -> 2, 3 -> 2, 3 is unreachable from 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        let result = dominators(&graph, node_ptr1.0.as_ref()).unwrap();
+        assert!(!result.is_reachable(node_ptr3.0.as_ref()), "3 is reachable");
+        assert!(
+            result
+                .get_immediate_dominator(node_ptr3.0.as_ref())
+                .is_none(),
+            "Unreachable node has a dominator"
+        );
+        assert_eq!(
+            *result
+                .get_immediate_dominator(node_ptr2.0.as_ref())
+                .unwrap()
+                .0
+                .borrow()
+                .get_value(),
+            1,
+            "idom(2) is not 1"
+        );
+
+        let undirected = Graph::<u32>::new(false);
+        assert!(
+            dominators(&undirected, node_ptr1.0.as_ref()).is_err(),
+            "Undirected graph was accepted"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use crate::alg::alg::{validate_endpoints, AlgorithmError};
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::rc::Rc;
+
+use priority_queue::PriorityQueue;
+
+const INFINITY: u64 = u64::MAX;
+
+/// Shortest path planner that repairs its previous search when edge weights change
+///
+/// Uses Lifelong Planning A* without a heuristic, which needs every edge weight to be
+/// positive. The planner works on a copy of the edge weights taken when it is created, so
+/// weight updates do not change the graph itself. After an update only the nodes whose
+/// distance changed are searched again
+pub struct IncrementalPlanner<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    directed: bool,
+    successors: Vec<Vec<(usize, u32)>>,
+    predecessors: Vec<Vec<(usize, u32)>>,
+    start: usize,
+    end: usize,
+    // Distance found so far and the one-step lookahead from predecessors
+    distance: Vec<u64>,
+    lookahead: Vec<u64>,
+    queue: PriorityQueue<usize, Reverse<u64>>,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> IncrementalPlanner<T> {
+    pub fn new(
+        graph: &Graph<T>,
+        start: &RefCell<Node<T>>,
+        end: &RefCell<Node<T>>,
+    ) -> Result<Self, AlgorithmError> {
+        let (start, end) = validate_endpoints(graph, start, end)?;
+
+        let successors = graph.get_adjacency();
+        if successors.iter().flatten().any(|&(_, weight)| weight == 0) {
+            return Err(zero_weight());
+        }
+
+        let mut predecessors = vec![Vec::new(); successors.len()];
+        for (u, edges) in successors.iter().enumerate() {
+            for &(v, weight) in edges {
+                predecessors[v].push((u, weight));
+            }
+        }
+
+        let n = successors.len();
+        let mut planner = Self {
+            nodes: graph
+                .get_nodes()
+                .iter()
+                .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+                .collect(),
+            directed: graph.is_directed(),
+            successors,
+            predecessors,
+            start,
+            end,
+            distance: vec![INFINITY; n],
+            lookahead: vec![INFINITY; n],
+            queue: PriorityQueue::new(),
+        };
+
+        planner.lookahead[start] = 0;
+        planner.queue.push(start, Reverse(0));
+
+        Ok(planner)
+    }
+
+    /// Returns the current shortest path from start to end, searching only where needed
+    pub fn find_path(&mut self) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
+        self.compute_shortest_path();
+
+        if self.distance[self.end] == INFINITY {
+            return Err(AlgorithmError::CannotFindPath("No path found".to_string()));
+        }
+
+        // Walk back from end through the predecessor that gives each distance
+        let mut path = vec![self.end];
+        let mut visited = vec![false; self.nodes.len()];
+        visited[self.end] = true;
+
+        let mut current = self.end;
+        while current != self.start {
+            current = self.predecessors[current]
+                .iter()
+                .filter(|(p, _)| !visited[*p] && self.distance[*p] != INFINITY)
+                .min_by_key(|(p, weight)| (self.distance[*p] + *weight as u64, self.distance[*p]))
+                .map(|(p, _)| *p)
+                .unwrap();
+
+            visited[current] = true;
+            path.push(current);
+        }
+        path.reverse();
+
+        Ok(path
+            .into_iter()
+            .map(|i| NodeRcWrapper(Rc::clone(&self.nodes[i].0)))
+            .collect())
+    }
+
+    /// Returns the current shortest distance from start to end
+    pub fn get_distance(&mut self) -> Result<u64, AlgorithmError> {
+        self.compute_shortest_path();
+
+        match self.distance[self.end] {
+            INFINITY => Err(AlgorithmError::CannotFindPath("No path found".to_string())),
+            d => Ok(d),
+        }
+    }
+
+    /// Changes the weight of an existing edge, in both directions for undirected graphs
+    ///
+    /// The search is repaired the next time a path or distance is requested
+    pub fn update_edge_weight(
+        &mut self,
+        from: &RefCell<Node<T>>,
+        to: &RefCell<Node<T>>,
+        weight: u32,
+    ) -> Result<(), AlgorithmError> {
+        if weight == 0 {
+            return Err(zero_weight());
+        }
+
+        let from = self.index_of(from)?;
+        let to = self.index_of(to)?;
+
+        if !self.successors[from].iter().any(|&(v, _)| v == to) {
+            return Err(AlgorithmError::InvalidArgument(
+                "Edge does not exist in graph".to_string(),
+            ));
+        }
+
+        self.set_weight(from, to, weight);
+        if !self.directed {
+            self.set_weight(to, from, weight);
+        }
+
+        Ok(())
+    }
+
+    fn set_weight(&mut self, from: usize, to: usize, weight: u32) {
+        for edge in self.successors[from].iter_mut().filter(|e| e.0 == to) {
+            edge.1 = weight;
+        }
+        for edge in self.predecessors[to].iter_mut().filter(|e| e.0 == from) {
+            edge.1 = weight;
+        }
+
+        self.update_node(to);
+    }
+
+    fn index_of(&self, node: &RefCell<Node<T>>) -> Result<usize, AlgorithmError> {
+        self.nodes
+            .iter()
+            .position(|n| *n.0.borrow().get_value() == *node.borrow().get_value())
+            .ok_or_else(|| {
+                AlgorithmError::InvalidArgument("Node does not exist in graph".to_string())
+            })
+    }
+
+    /// Recomputes the lookahead of node and queues it if it no longer matches its distance
+    fn update_node(&mut self, node: usize) {
+        if node != self.start {
+            self.lookahead[node] = self.predecessors[node]
+                .iter()
+                .filter(|(p, _)| self.distance[*p] != INFINITY)
+                .map(|(p, weight)| self.distance[*p] + *weight as u64)
+                .min()
+                .unwrap_or(INFINITY);
+        }
+
+        self.queue.remove(&node);
+        if self.distance[node] != self.lookahead[node] {
+            self.queue
+                .push(node, Reverse(self.distance[node].min(self.lookahead[node])));
+        }
+    }
+
+    fn compute_shortest_path(&mut self) {
+        loop {
+            let target = self.distance[self.end].min(self.lookahead[self.end]);
+            match self.queue.peek() {
+                Some((_, Reverse(key)))
+                    if *key < target || self.distance[self.end] != self.lookahead[self.end] => {}
+                _ => break,
+            }
+
+            let (node, _) = self.queue.pop().unwrap();
+
+            if self.distance[node] > self.lookahead[node] {
+                // Distance went down, settle it
+                self.distance[node] = self.lookahead[node];
+            } else {
+                // Distance went up, forget it and let the lookahead find the new one
+                self.distance[node] = INFINITY;
+                self.update_node(node);
+            }
+
+            for i in 0..self.successors[node].len() {
+                let next = self.successors[node][i].0;
+                self.update_node(next);
+            }
+        }
+    }
+}
+
+/// Nodes on a zero weight cycle could keep vouching for each other's stale distance
+fn zero_weight() -> AlgorithmError {
+    AlgorithmError::InvalidArgument("Edge weights must be positive".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, NodeRcWrapper};
+
+    use super::IncrementalPlanner;
+
+    fn values(path: &[NodeRcWrapper<u32>]) -> Vec<u32> {
+        path.iter().map(|n| *n.0.borrow().get_value()).collect()
+    }
+
+    #[test]
+    fn replans_after_updates() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 4 costs 2, 1 -> 3 -> 4 costs 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 2);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 2);
+
+        let mut planner =
+            IncrementalPlanner::new(&graph, node_ptr1.0.as_ref(), node_ptr4.0.as_ref()).unwrap();
+        assert_eq!(
+            values(&planner.find_path().unwrap()),
+            vec![1, 2, 4],
+            "Initial path is not 1, 2, 4"
+        );
+
+        // Blocking 2 -> 4 moves the path to 3
+        planner
+            .update_edge_weight(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 10)
+            .unwrap();
+        assert_eq!(
+            values(&planner.find_path().unwrap()),
+            vec![1, 3, 4],
+            "Path after increase is not 1, 3, 4"
+        );
+        assert_eq!(planner.get_distance().unwrap(), 4, "Distance is not 4");
+
+        // Partly clearing 2 -> 4 brings 2 back, 1 + 2 is less than 4
+        planner
+            .update_edge_weight(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 2)
+            .unwrap();
+        assert_eq!(
+            values(&planner.find_path().unwrap()),
+            vec![1, 2, 4],
+            "Path after decrease is not 1, 2, 4"
+        );
+        assert_eq!(planner.get_distance().unwrap(), 3, "Distance is not 3");
+    }
+
+    #[test]
+    fn missing_edge() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 - 2, 3 is isolated
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        let mut planner =
+            IncrementalPlanner::new(&graph, node_ptr1.0.as_ref(), node_ptr3.0.as_ref()).unwrap();
+        assert!(planner.find_path().is_err(), "Path to isolated node found");
+        assert!(
+            planner
+                .update_edge_weight(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1)
+                .is_err(),
+            "Missing edge was updated"
+        );
+        assert!(
+            planner
+                .update_edge_weight(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 0)
+                .is_err(),
+            "Zero weight was accepted"
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Decides whether two node values may be mapped onto each other
+pub type NodeMatcher<'a, T> = &'a dyn Fn(&T, &T) -> bool;
+
+/// Decides whether two edge weights may be mapped onto each other
+pub type EdgeMatcher<'a> = &'a dyn Fn(u32, u32) -> bool;
+
+/// Iterator over the mappings between two graphs found by VF2
+///
+/// Created by isomorphisms() or subgraph_isomorphisms(). Each mapping pairs every node of
+/// the second graph with the node of the first graph it is mapped to, as
+/// (first graph node, second graph node)
+pub struct Isomorphisms<'a, T: std::cmp::PartialEq + std::hash::Hash> {
+    first: GraphState<T>,
+    second: GraphState<T>,
+    node_match: Option<NodeMatcher<'a, T>>,
+    edge_match: Option<EdgeMatcher<'a>>,
+    subgraph: bool,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+/// Nodes, edges and the VF2 bookkeeping for one of the two graphs
+struct GraphState<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    successors: Vec<HashMap<usize, u32>>,
+    predecessors: Vec<HashMap<usize, u32>>,
+    core: Vec<Option<usize>>,
+    // Depth at which each node joined the terminal sets, 0 if it has not
+    in_depth: Vec<usize>,
+    out_depth: Vec<usize>,
+}
+
+/// One level of the VF2 search
+struct Frame {
+    candidates: Vec<(usize, usize)>,
+    next: usize,
+    applied: Option<(usize, usize)>,
+}
+
+/// Returns true if the two graphs are isomorphic
+///
+/// If given, node_match must accept the values of every pair of mapped nodes and
+/// edge_match the weights of every pair of mapped edges
+pub fn is_isomorphic<T: std::cmp::PartialEq + std::hash::Hash>(
+    first: &Graph<T>,
+    second: &Graph<T>,
+    node_match: Option<NodeMatcher<T>>,
+    edge_match: Option<EdgeMatcher>,
+) -> bool {
+    isomorphisms(first, second, node_match, edge_match)
+        .next()
+        .is_some()
+}
+
+/// Returns true if pattern is isomorphic to a node-induced subgraph of graph
+///
+/// The subgraph is induced, so any edge between two mapped nodes of graph must also
+/// exist in pattern
+pub fn is_subgraph_isomorphic<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    pattern: &Graph<T>,
+    node_match: Option<NodeMatcher<T>>,
+    edge_match: Option<EdgeMatcher>,
+) -> bool {
+    subgraph_isomorphisms(graph, pattern, node_match, edge_match)
+        .next()
+        .is_some()
+}
+
+/// Returns an iterator over every isomorphism between the two graphs
+pub fn isomorphisms<'a, T: std::cmp::PartialEq + std::hash::Hash>(
+    first: &Graph<T>,
+    second: &Graph<T>,
+    node_match: Option<NodeMatcher<'a, T>>,
+    edge_match: Option<EdgeMatcher<'a>>,
+) -> Isomorphisms<'a, T> {
+    Isomorphisms::new(first, second, node_match, edge_match, false)
+}
+
+/// Returns an iterator over every mapping of pattern onto a node-induced subgraph of graph
+pub fn subgraph_isomorphisms<'a, T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    pattern: &Graph<T>,
+    node_match: Option<NodeMatcher<'a, T>>,
+    edge_match: Option<EdgeMatcher<'a>>,
+) -> Isomorphisms<'a, T> {
+    Isomorphisms::new(graph, pattern, node_match, edge_match, true)
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> GraphState<T> {
+    fn new(graph: &Graph<T>) -> GraphState<T> {
+        let adjacency = graph.get_adjacency();
+        let n = adjacency.len();
+
+        let mut successors = vec![HashMap::new(); n];
+        let mut predecessors = vec![HashMap::new(); n];
+        for (i, edges) in adjacency.into_iter().enumerate() {
+            for (j, w) in edges {
+                successors[i].insert(j, w);
+                predecessors[j].insert(i, w);
+            }
+        }
+
+        GraphState {
+            nodes: graph
+                .get_nodes()
+                .iter()
+                .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+                .collect(),
+            successors,
+            predecessors,
+            core: vec![None; n],
+            in_depth: vec![0; n],
+            out_depth: vec![0; n],
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        self.successors.iter().map(|s| s.len()).sum()
+    }
+
+    fn in_terminal(&self, n: usize) -> bool {
+        self.in_depth[n] != 0 && self.core[n].is_none()
+    }
+
+    fn out_terminal(&self, n: usize) -> bool {
+        self.out_depth[n] != 0 && self.core[n].is_none()
+    }
+
+    fn add_pair(&mut self, node: usize, other: usize, depth: usize) {
+        self.core[node] = Some(other);
+
+        if self.in_depth[node] == 0 {
+            self.in_depth[node] = depth;
+        }
+        if self.out_depth[node] == 0 {
+            self.out_depth[node] = depth;
+        }
+
+        for &p in self.predecessors[node].keys() {
+            if self.in_depth[p] == 0 {
+                self.in_depth[p] = depth;
+            }
+        }
+        for &s in self.successors[node].keys() {
+            if self.out_depth[s] == 0 {
+                self.out_depth[s] = depth;
+            }
+        }
+    }
+
+    fn remove_pair(&mut self, node: usize, depth: usize) {
+        self.core[node] = None;
+
+        for d in self.in_depth.iter_mut().chain(self.out_depth.iter_mut()) {
+            if *d == depth {
+                *d = 0;
+            }
+        }
+    }
+
+    /// Counts neighbours of node in each lookahead class:
+    /// (in terminal, out terminal, in neither and not mapped)
+    fn lookahead(&self, neighbours: &HashMap<usize, u32>) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for &m in neighbours.keys() {
+            if self.in_terminal(m) {
+                counts.0 += 1;
+            }
+            if self.out_terminal(m) {
+                counts.1 += 1;
+            }
+            if self.in_depth[m] == 0 && self.out_depth[m] == 0 {
+                counts.2 += 1;
+            }
+        }
+
+        counts
+    }
+}
+
+impl<'a, T: std::cmp::PartialEq + std::hash::Hash> Isomorphisms<'a, T> {
+    fn new(
+        first: &Graph<T>,
+        second: &Graph<T>,
+        node_match: Option<NodeMatcher<'a, T>>,
+        edge_match: Option<EdgeMatcher<'a>>,
+        subgraph: bool,
+    ) -> Isomorphisms<'a, T> {
+        let mut isomorphisms = Isomorphisms {
+            first: GraphState::new(first),
+            second: GraphState::new(second),
+            node_match,
+            edge_match,
+            subgraph,
+            stack: Vec::new(),
+            started: false,
+        };
+
+        // Rule out graphs that can never match before searching
+        let n1 = isomorphisms.first.nodes.len();
+        let n2 = isomorphisms.second.nodes.len();
+        let impossible = first.is_directed() != second.is_directed()
+            || (subgraph && n1 < n2)
+            || (!subgraph
+                && (n1 != n2
+                    || isomorphisms.first.edge_count() != isomorphisms.second.edge_count()));
+
+        if impossible {
+            isomorphisms.started = true;
+        }
+
+        isomorphisms
+    }
+
+    fn depth(&self) -> usize {
+        self.second.core.iter().filter(|c| c.is_some()).count()
+    }
+
+    /// Pairs to try next, following the terminal sets so the mapping grows connected
+    fn candidates(&self) -> Vec<(usize, usize)> {
+        let n1 = self.first.nodes.len();
+        let n2 = self.second.nodes.len();
+
+        let first_out: Vec<usize> = (0..n1).filter(|&n| self.first.out_terminal(n)).collect();
+        let second_out = (0..n2).find(|&n| self.second.out_terminal(n));
+        if let (false, Some(m)) = (first_out.is_empty(), second_out) {
+            return first_out.into_iter().map(|n| (n, m)).collect();
+        }
+
+        let first_in: Vec<usize> = (0..n1).filter(|&n| self.first.in_terminal(n)).collect();
+        let second_in = (0..n2).find(|&n| self.second.in_terminal(n));
+        if let (false, Some(m)) = (first_in.is_empty(), second_in) {
+            return first_in.into_iter().map(|n| (n, m)).collect();
+        }
+
+        // Otherwise start a new component, pairing the next unmapped node of the second
+        // graph with every unmapped node of the first
+        match (0..n2).find(|&n| self.second.core[n].is_none()) {
+            Some(m) => (0..n1)
+                .filter(|&n| self.first.core[n].is_none())
+                .map(|n| (n, m))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn feasible(&self, n1: usize, n2: usize) -> bool {
+        let first = &self.first;
+        let second = &self.second;
+
+        if let Some(node_match) = self.node_match {
+            if !node_match(
+                first.nodes[n1].0.borrow().get_value(),
+                second.nodes[n2].0.borrow().get_value(),
+            ) {
+                return false;
+            }
+        }
+
+        // Self loops must agree
+        let loop1 = first.successors[n1].get(&n1);
+        let loop2 = second.successors[n2].get(&n2);
+        match (loop1, loop2) {
+            (None, None) => {}
+            (Some(&w1), Some(&w2)) => {
+                if !self.weights_match(w1, w2) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
+        // Edges to already mapped nodes must exist in both graphs
+        for (edges1, edges2) in [
+            (&first.predecessors[n1], &second.predecessors[n2]),
+            (&first.successors[n1], &second.successors[n2]),
+        ] {
+            for (&m1, &w1) in edges1 {
+                if let Some(m2) = first.core[m1] {
+                    match edges2.get(&m2) {
+                        Some(&w2) if self.weights_match(w1, w2) => {}
+                        _ => return false,
+                    }
+                }
+            }
+            for &m2 in edges2.keys() {
+                if let Some(m1) = second.core[m2] {
+                    if !edges1.contains_key(&m1) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        // Lookahead, the first graph must have at least as many ways to continue
+        for (edges1, edges2) in [
+            (&first.predecessors[n1], &second.predecessors[n2]),
+            (&first.successors[n1], &second.successors[n2]),
+        ] {
+            let counts1 = first.lookahead(edges1);
+            let counts2 = second.lookahead(edges2);
+
+            let ok = if self.subgraph {
+                counts1.0 >= counts2.0 && counts1.1 >= counts2.1 && counts1.2 >= counts2.2
+            } else {
+                counts1 == counts2
+            };
+
+            if !ok {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn weights_match(&self, w1: u32, w2: u32) -> bool {
+        self.edge_match.is_none_or(|edge_match| edge_match(w1, w2))
+    }
+
+    fn mapping(&self) -> Vec<(NodeRcWrapper<T>, NodeRcWrapper<T>)> {
+        self.second
+            .core
+            .iter()
+            .enumerate()
+            .filter_map(|(n2, n1)| {
+                n1.map(|n1| {
+                    (
+                        NodeRcWrapper(Rc::clone(&self.first.nodes[n1].0)),
+                        NodeRcWrapper(Rc::clone(&self.second.nodes[n2].0)),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'a, T: std::cmp::PartialEq + std::hash::Hash> Iterator for Isomorphisms<'a, T> {
+    type Item = Vec<(NodeRcWrapper<T>, NodeRcWrapper<T>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+
+            // An empty pattern has exactly one, empty, mapping
+            if self.second.nodes.is_empty() {
+                return Some(Vec::new());
+            }
+
+            self.stack.push(Frame {
+                candidates: self.candidates(),
+                next: 0,
+                applied: None,
+            });
+        }
+
+        loop {
+            let depth = self.depth();
+            let frame = self.stack.last_mut()?;
+
+            // Undo the pair tried last time at this level
+            if let Some((n1, n2)) = frame.applied.take() {
+                self.first.remove_pair(n1, depth);
+                self.second.remove_pair(n2, depth);
+                continue;
+            }
+
+            if frame.next == frame.candidates.len() {
+                self.stack.pop();
+                continue;
+            }
+
+            let (n1, n2) = frame.candidates[frame.next];
+            frame.next += 1;
+
+            if !self.feasible(n1, n2) {
+                continue;
+            }
+
+            self.first.add_pair(n1, n2, depth + 1);
+            self.second.add_pair(n2, n1, depth + 1);
+            self.stack.last_mut().unwrap().applied = Some((n1, n2));
+
+            if depth + 1 == self.second.nodes.len() {
+                return Some(self.mapping());
+            }
+
+            self.stack.push(Frame {
+                candidates: self.candidates(),
+                next: 0,
+                applied: None,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{is_isomorphic, is_subgraph_isomorphic, isomorphisms, subgraph_isomorphisms};
+
+    #[test]
+    fn isomorphic_cycles() {
+        // Square 1 - 2 - 3 - 4 - 1 with one heavy edge
+        let mut first = Graph::<u32>::new(false);
+        let node_ptr1 = first.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = first.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = first.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = first.add_node(Node::new(4)).expect("Failed to add node");
+        first.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 5);
+        first.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        first.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        first.add_edge(node_ptr4.0.as_ref(), node_ptr1.0.as_ref(), 1);
+
+        // Same square with nodes labelled differently, a - c - b - d - a
+        let mut second = Graph::<u32>::new(false);
+        let node_a = second.add_node(Node::new(10)).expect("Failed to add node");
+        let node_b = second.add_node(Node::new(20)).expect("Failed to add node");
+        let node_c = second.add_node(Node::new(30)).expect("Failed to add node");
+        let node_d = second.add_node(Node::new(40)).expect("Failed to add node");
+        second.add_edge(node_a.0.as_ref(), node_c.0.as_ref(), 1);
+        second.add_edge(node_c.0.as_ref(), node_b.0.as_ref(), 1);
+        second.add_edge(node_b.0.as_ref(), node_d.0.as_ref(), 5);
+        second.add_edge(node_d.0.as_ref(), node_a.0.as_ref(), 1);
+
+        assert!(
+            is_isomorphic(&first, &second, None, None),
+            "Squares are not isomorphic"
+        );
+
+        // A square has 8 automorphisms, but only 2 keep the heavy edge in place
+        assert_eq!(isomorphisms(&first, &second, None, None).count(), 8);
+        let equal = |a: u32, b: u32| a == b;
+        assert_eq!(
+            isomorphisms(&first, &second, None, Some(&equal)).count(),
+            2,
+            "Edge matcher ignored"
+        );
+
+        // Node matcher that can never succeed
+        let never = |_: &u32, _: &u32| false;
+        assert!(
+            !is_isomorphic(&first, &second, Some(&never), None),
+            "Node matcher ignored"
+        );
+
+        // Adding a chord breaks the isomorphism
+        second.add_edge(node_a.0.as_ref(), node_b.0.as_ref(), 1);
+        assert!(
+            !is_isomorphic(&first, &second, None, None),
+            "Different graphs are isomorphic"
+        );
+    }
+
+    #[test]
+    fn directed_subgraph() {
+        // 1 -> 2 -> 3 -> 1 cycle with a tail 3 -> 4
+        let mut graph = Graph::<u32>::new(true);
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        // Directed triangle pattern
+        let mut pattern = Graph::<u32>::new(true);
+        let node_a = pattern.add_node(Node::new(1)).expect("Failed to add node");
+        let node_b = pattern.add_node(Node::new(2)).expect("Failed to add node");
+        let node_c = pattern.add_node(Node::new(3)).expect("Failed to add node");
+        pattern.add_edge(node_a.0.as_ref(), node_b.0.as_ref(), 1);
+        pattern.add_edge(node_b.0.as_ref(), node_c.0.as_ref(), 1);
+        pattern.add_edge(node_c.0.as_ref(), node_a.0.as_ref(), 1);
+
+        assert!(
+            is_subgraph_isomorphic(&graph, &pattern, None, None),
+            "Triangle not found"
+        );
+        assert_eq!(
+            subgraph_isomorphisms(&graph, &pattern, None, None).count(),
+            3,
+            "Expected one mapping per rotation of the triangle"
+        );
+
+        // Only the identity mapping keeps node values equal
+        let same = |a: &u32, b: &u32| a == b;
+        let mappings: Vec<_> = subgraph_isomorphisms(&graph, &pattern, Some(&same), None).collect();
+        assert_eq!(mappings.len(), 1, "Node matcher ignored");
+        for (from, to) in &mappings[0] {
+            assert_eq!(
+                from.0.borrow().get_value(),
+                to.0.borrow().get_value(),
+                "Mapping incorrect"
+            );
+        }
+
+        // Reversing one pattern edge means no directed match remains
+        let mut reversed = Graph::<u32>::new(true);
+        let node_a = reversed.add_node(Node::new(1)).expect("Failed to add node");
+        let node_b = reversed.add_node(Node::new(2)).expect("Failed to add node");
+        let node_c = reversed.add_node(Node::new(3)).expect("Failed to add node");
+        reversed.add_edge(node_a.0.as_ref(), node_b.0.as_ref(), 1);
+        reversed.add_edge(node_b.0.as_ref(), node_c.0.as_ref(), 1);
+        reversed.add_edge(node_a.0.as_ref(), node_c.0.as_ref(), 1);
+        assert!(
+            !is_subgraph_isomorphic(&graph, &reversed, None, None),
+            "Transitive triangle found in a directed cycle"
+        );
+    }
+
+    #[test]
+    fn disconnected_pattern() {
+        // Path 1 - 2 - 3 and isolated 4
+        let mut graph = Graph::<u32>::new(false);
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        graph.add_node(Node::new(4)).expect("Failed to add node");
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        // Edge a - b and isolated c, which can only map to 4 once a - b is placed
+        let mut pattern = Graph::<u32>::new(false);
+        let node_a = pattern.add_node(Node::new(1)).expect("Failed to add node");
+        let node_b = pattern.add_node(Node::new(2)).expect("Failed to add node");
+        pattern.add_node(Node::new(3)).expect("Failed to add node");
+        pattern.add_edge(node_a.0.as_ref(), node_b.0.as_ref(), 1);
+
+        assert!(
+            is_subgraph_isomorphic(&graph, &pattern, None, None),
+            "Disconnected pattern not found"
+        );
+
+        // a - b maps onto 1 - 2 or 2 - 3 either way round, and the subgraph is induced so
+        // c can never map to the remaining end of the path
+        let mappings: Vec<_> = subgraph_isomorphisms(&graph, &pattern, None, None).collect();
+        assert_eq!(mappings.len(), 4, "Expected 4 mappings");
+        for mapping in &mappings {
+            let c = mapping
+                .iter()
+                .find(|(_, to)| *to.0.borrow().get_value() == 3)
+                .map(|(from, _)| *from.0.borrow().get_value());
+            assert_eq!(c, Some(4), "Isolated node not mapped to 4");
+        }
+    }
+}
@@ -1,9 +1,32 @@
 // Public modules
+#[allow(clippy::module_inception)]
 pub mod alg;
+pub mod arborescence;
+pub mod centrality;
+pub mod clique;
+pub mod clustering;
+pub mod coloring;
+pub mod community;
+pub mod cuts;
+pub mod cycles;
+pub mod dominators;
+pub mod incremental;
+pub mod isomorphism;
+pub mod link_analysis;
+pub mod mean_cycle;
+pub mod metrics;
+pub mod paths;
+pub mod scheduling;
+pub mod time_dependent;
+pub mod transitive;
+pub mod two_sat;
 
 /// Contains all the structures for creating a graph with nodes and edges
 pub mod graph {
+    use crate::alg::alg::AlgorithmError;
+
     use std::cell::RefCell;
+    use std::collections::HashMap;
     use std::rc::Rc;
 
     /// Wrapper for Node to allow for Rc<RefCell<Node<T>>> to implement Hash
@@ -111,6 +134,80 @@ pub mod graph {
                 .find(|n| *n.0.borrow().get_value() == *node.borrow().get_value())
                 .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
         }
+
+        pub fn is_directed(&self) -> bool {
+            self.directed
+        }
+
+        /// Returns the position of the node in get_nodes(), if it exists
+        pub fn get_node_index(&self, node: &RefCell<Node<T>>) -> Option<usize> {
+            self.nodes
+                .iter()
+                .position(|n| *n.0.borrow().get_value() == *node.borrow().get_value())
+        }
+
+        /// Returns the adjacency list of the graph, indexed the same as get_nodes()
+        ///
+        /// Each entry holds (index of neighbour, edge weight) for every edge leaving the node
+        pub fn get_adjacency(&self) -> Vec<Vec<(usize, u32)>> {
+            // Nodes are shared between the graph and its edges, so pointers identify them
+            let mut indices = HashMap::<*const RefCell<Node<T>>, usize>::new();
+            for (i, n) in self.nodes.iter().enumerate() {
+                indices.insert(Rc::as_ptr(&n.0), i);
+            }
+
+            self.nodes
+                .iter()
+                .map(|n| {
+                    n.0.borrow()
+                        .get_edges()
+                        .iter()
+                        .filter_map(|e| {
+                            indices
+                                .get(&Rc::as_ptr(&e.get_node().0))
+                                .map(|&i| (i, e.get_weight()))
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+
+        /// Returns the neighbours of every node of an undirected graph, ignoring self loops
+        /// and weights
+        pub(crate) fn get_undirected_neighbours<N: FromIterator<usize>>(
+            &self,
+        ) -> Result<Vec<N>, AlgorithmError> {
+            if self.directed {
+                return Err(AlgorithmError::InvalidGraph(
+                    "Graph must be undirected".to_string(),
+                ));
+            }
+
+            Ok(self
+                .get_adjacency()
+                .into_iter()
+                .enumerate()
+                .map(|(i, edges)| {
+                    edges
+                        .into_iter()
+                        .map(|(j, _)| j)
+                        .filter(|&j| j != i)
+                        .collect()
+                })
+                .collect())
+        }
+
+        /// Maps the value of every node to the entry at its position in values
+        pub(crate) fn key_by_value<V>(&self, values: impl IntoIterator<Item = V>) -> HashMap<T, V>
+        where
+            T: Eq + Clone,
+        {
+            self.nodes
+                .iter()
+                .zip(values)
+                .map(|(n, v)| (n.0.borrow().get_value().clone(), v))
+                .collect()
+        }
     }
 
     impl<T: std::cmp::PartialEq + std::hash::Hash> std::hash::Hash for NodeRcWrapper<T> {
@@ -246,26 +343,22 @@ mod test {
         graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
 
         // Check that nodes exist
-        assert_eq!(
+        assert!(
             graph.exists(node_ptr1.0.as_ref()),
-            true,
             "Node 1 does not exist in graph"
         );
-        assert_eq!(
+        assert!(
             graph.exists(node_ptr2.0.as_ref()),
-            true,
             "Node 2 does not exist in graph"
         );
 
         // Check that we can get nodes
-        assert_eq!(
+        assert!(
             graph.get_node(node_ptr1.0.as_ref()).is_some(),
-            true,
             "Failed to get node 1"
         );
-        assert_eq!(
+        assert!(
             graph.get_node(node_ptr2.0.as_ref()).is_some(),
-            true,
             "Failed to get node 2"
         );
 
@@ -312,25 +405,21 @@ mod test {
         graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
 
         // Check that nodes exist
-        assert_eq!(
+        assert!(
             graph.exists(node_ptr1.0.as_ref()),
-            true,
             "Node 1 does not exist in graph"
         );
-        assert_eq!(
+        assert!(
             graph.exists(node_ptr2.0.as_ref()),
-            true,
             "Node 2 does not exist in graph"
         );
 
-        assert_eq!(
+        assert!(
             graph.get_node(node_ptr1.0.as_ref()).is_some(),
-            true,
             "Failed to get node 1"
         );
-        assert_eq!(
+        assert!(
             graph.get_node(node_ptr2.0.as_ref()).is_some(),
-            true,
             "Failed to get node 2"
         );
 
@@ -0,0 +1,433 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, Node};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Where the rank of nodes without outgoing edges is sent on each iteration
+pub enum DanglingStrategy {
+    /// Spread evenly over every node in the graph
+    Uniform,
+    /// Spread the same way as the teleport step, so personalized runs
+    /// send it back to the seed nodes
+    Personalization,
+}
+
+/// Settings shared by pagerank and personalized_pagerank
+///
+/// Defaults are a damping factor of 0.85, a tolerance of 1e-6, 100 iterations,
+/// uniform dangling node handling and unweighted transitions
+pub struct PageRankConfig {
+    damping: f64,
+    tolerance: f64,
+    max_iterations: usize,
+    dangling: DanglingStrategy,
+    weighted: bool,
+}
+
+impl PageRankConfig {
+    pub fn new() -> PageRankConfig {
+        PageRankConfig {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+            dangling: DanglingStrategy::Uniform,
+            weighted: false,
+        }
+    }
+
+    /// Probability of following an edge rather than teleporting, must be in [0, 1)
+    pub fn set_damping(&mut self, damping: f64) {
+        self.damping = damping;
+    }
+
+    /// Iteration stops once the total change of all ranks is below tolerance
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    pub fn set_dangling(&mut self, dangling: DanglingStrategy) {
+        self.dangling = dangling;
+    }
+
+    /// If set, edges are followed in proportion to their weight instead of uniformly
+    pub fn set_weighted(&mut self, weighted: bool) {
+        self.weighted = weighted;
+    }
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// PageRank of every node, keyed by node value. Ranks sum to 1
+///
+/// Undirected graphs are treated as having an edge in each direction
+pub fn pagerank<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    config: &PageRankConfig,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    let n = graph.get_nodes().len();
+
+    run_pagerank(graph, vec![1.0 / n as f64; n], config)
+}
+
+/// PageRank where every teleport lands on one of the seed nodes
+///
+/// Ranks measure how close each node is to the seeds rather than global importance
+pub fn personalized_pagerank<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    seeds: &[&RefCell<Node<T>>],
+    config: &PageRankConfig,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    if seeds.is_empty() {
+        return Err(AlgorithmError::InvalidArgument(
+            "Seed set is empty".to_string(),
+        ));
+    }
+
+    let mut personalization = vec![0.0; graph.get_nodes().len()];
+    for seed in seeds {
+        let index = graph.get_node_index(seed).ok_or_else(|| {
+            AlgorithmError::InvalidArgument("Seed node does not exist in graph".to_string())
+        })?;
+        personalization[index] = 1.0;
+    }
+
+    let total: f64 = personalization.iter().sum();
+    for p in personalization.iter_mut() {
+        *p /= total;
+    }
+
+    run_pagerank(graph, personalization, config)
+}
+
+/// Settings for hits
+///
+/// Defaults are a tolerance of 1e-6, 100 iterations and unweighted edges
+pub struct HitsConfig {
+    tolerance: f64,
+    max_iterations: usize,
+    weighted: bool,
+}
+
+impl HitsConfig {
+    pub fn new() -> HitsConfig {
+        HitsConfig {
+            tolerance: 1e-6,
+            max_iterations: 100,
+            weighted: false,
+        }
+    }
+
+    /// Iteration stops once the total change of all hub scores is below tolerance
+    pub fn set_tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations;
+    }
+
+    /// If set, edge weights scale each contribution
+    pub fn set_weighted(&mut self, weighted: bool) {
+        self.weighted = weighted;
+    }
+}
+
+impl Default for HitsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hub and authority scores from HITS, each keyed by node value
+///
+/// Both sum to 1, unless the graph has no edges and every score is 0
+pub struct HitsScores<T: std::cmp::Eq + std::hash::Hash> {
+    hubs: HashMap<T, f64>,
+    authorities: HashMap<T, f64>,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> HitsScores<T> {
+    pub fn get_hubs(&self) -> &HashMap<T, f64> {
+        &self.hubs
+    }
+
+    pub fn get_authorities(&self) -> &HashMap<T, f64> {
+        &self.authorities
+    }
+}
+
+/// Hub and authority scores computed with HITS
+///
+/// A good hub points to many good authorities, a good authority is pointed to by many good
+/// hubs
+pub fn hits<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    config: &HitsConfig,
+) -> Result<HitsScores<T>, AlgorithmError> {
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+
+    if n == 0 {
+        return Ok(HitsScores {
+            hubs: HashMap::new(),
+            authorities: HashMap::new(),
+        });
+    }
+
+    let weight = |w: u32| if config.weighted { w as f64 } else { 1.0 };
+
+    let mut hubs = vec![1.0 / n as f64; n];
+    let mut authorities = vec![0.0; n];
+
+    for _ in 0..config.max_iterations {
+        // Authorities collect from the hubs pointing at them
+        authorities = vec![0.0; n];
+        for (i, edges) in adjacency.iter().enumerate() {
+            for &(j, w) in edges {
+                authorities[j] += hubs[i] * weight(w);
+            }
+        }
+        normalize(&mut authorities);
+
+        // Hubs collect from the authorities they point at
+        let mut next = vec![0.0; n];
+        for (i, edges) in adjacency.iter().enumerate() {
+            for &(j, w) in edges {
+                next[i] += authorities[j] * weight(w);
+            }
+        }
+        normalize(&mut next);
+
+        let change: f64 = next.iter().zip(&hubs).map(|(a, b)| (a - b).abs()).sum();
+        hubs = next;
+
+        if change < config.tolerance {
+            return Ok(HitsScores {
+                hubs: graph.key_by_value(hubs),
+                authorities: graph.key_by_value(authorities),
+            });
+        }
+    }
+
+    Err(AlgorithmError::DidNotConverge(format!(
+        "HITS did not converge in {} iterations",
+        config.max_iterations
+    )))
+}
+
+fn run_pagerank<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    personalization: Vec<f64>,
+    config: &PageRankConfig,
+) -> Result<HashMap<T, f64>, AlgorithmError> {
+    if !(0.0..1.0).contains(&config.damping) {
+        return Err(AlgorithmError::InvalidArgument(
+            "Damping factor must be in [0, 1)".to_string(),
+        ));
+    }
+
+    let adjacency = graph.get_adjacency();
+    let n = adjacency.len();
+
+    if n == 0 {
+        return Ok(HashMap::new());
+    }
+
+    // Probability of following each edge, nodes with no usable edges are dangling
+    let transitions: Vec<Vec<(usize, f64)>> = adjacency
+        .iter()
+        .map(|edges| {
+            let weight = |w: u32| if config.weighted { w as f64 } else { 1.0 };
+            let total: f64 = edges.iter().map(|&(_, w)| weight(w)).sum();
+
+            if total == 0.0 {
+                Vec::new()
+            } else {
+                edges.iter().map(|&(j, w)| (j, weight(w) / total)).collect()
+            }
+        })
+        .collect();
+
+    let dangling_distribution = match config.dangling {
+        DanglingStrategy::Uniform => vec![1.0 / n as f64; n],
+        DanglingStrategy::Personalization => personalization.clone(),
+    };
+
+    let mut ranks = vec![1.0 / n as f64; n];
+
+    for _ in 0..config.max_iterations {
+        let dangling_rank: f64 = (0..n)
+            .filter(|&i| transitions[i].is_empty())
+            .map(|i| ranks[i])
+            .sum();
+
+        // Teleport and dangling contributions
+        let mut next: Vec<f64> = (0..n)
+            .map(|j| {
+                (1.0 - config.damping) * personalization[j]
+                    + config.damping * dangling_rank * dangling_distribution[j]
+            })
+            .collect();
+
+        // Contributions along edges
+        for (i, edges) in transitions.iter().enumerate() {
+            for &(j, p) in edges {
+                next[j] += config.damping * ranks[i] * p;
+            }
+        }
+
+        let change: f64 = next.iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+        ranks = next;
+
+        if change < config.tolerance {
+            return Ok(graph.key_by_value(ranks));
+        }
+    }
+
+    Err(AlgorithmError::DidNotConverge(format!(
+        "PageRank did not converge in {} iterations",
+        config.max_iterations
+    )))
+}
+
+/// Scales values to sum to 1, leaving all zero vectors untouched
+fn normalize(values: &mut [f64]) {
+    let total: f64 = values.iter().sum();
+    if total > 0.0 {
+        for v in values.iter_mut() {
+            *v /= total;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{hits, pagerank, personalized_pagerank, HitsConfig, PageRankConfig};
+
+    #[test]
+    fn pagerank_cycle_and_sink() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 3 -> 1 cycle, 1 -> 4 dangling
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let ranks = pagerank(&graph, &PageRankConfig::new()).unwrap();
+
+        let total: f64 = ranks.values().sum();
+        assert!((total - 1.0).abs() < 1e-6, "Ranks do not sum to 1");
+
+        // Node 1 receives the whole rank of 3, which receives the whole rank of 2
+        assert!(ranks[&1] > ranks[&2], "Node 1 not ranked above node 2");
+        assert!(ranks[&3] > ranks[&2], "Node 3 not ranked above node 2");
+
+        // Weighting 1 -> 4 heavily moves rank from the cycle to the sink
+        let mut weighted = Graph::<u32>::new(true);
+        let node_ptr1 = weighted.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = weighted.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr4 = weighted.add_node(Node::new(4)).expect("Failed to add node");
+        weighted.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        weighted.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        weighted.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 9);
+
+        let mut config = PageRankConfig::new();
+        let unweighted_ranks = pagerank(&weighted, &config).unwrap();
+        config.set_weighted(true);
+        let weighted_ranks = pagerank(&weighted, &config).unwrap();
+        assert!(
+            weighted_ranks[&4] > unweighted_ranks[&4],
+            "Edge weights were not used"
+        );
+    }
+
+    #[test]
+    fn personalized() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 - 2 - 3 - 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let config = PageRankConfig::new();
+        let ranks = personalized_pagerank(&graph, &[node_ptr1.0.as_ref()], &config).unwrap();
+
+        // Ranks fall off with distance from the seed
+        assert!(ranks[&1] > ranks[&3], "Seed not ranked above node 3");
+        assert!(ranks[&3] > ranks[&4], "Node 3 not ranked above node 4");
+
+        assert!(
+            personalized_pagerank(&graph, &[], &config).is_err(),
+            "Empty seed set accepted"
+        );
+    }
+
+    #[test]
+    fn hubs_and_authorities() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 and 2 both point at 3 and 4, 1 also points at 2
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        let mut config = HitsConfig::new();
+        config.set_tolerance(1e-9);
+        let scores = hits(&graph, &config).unwrap();
+        let hubs = scores.get_hubs();
+        let authorities = scores.get_authorities();
+
+        assert!(hubs[&1] > hubs[&2], "Node 1 is not the best hub");
+        assert_eq!(hubs[&3], 0.0, "Node 3 is a hub");
+        assert!(
+            authorities[&3] > authorities[&2],
+            "Node 3 is not a better authority than node 2"
+        );
+        assert_eq!(authorities[&1], 0.0, "Node 1 is an authority");
+
+        // Without edges there are no hubs or authorities
+        let mut edgeless = Graph::<u32>::new(true);
+        edgeless.add_node(Node::new(1)).expect("Failed to add node");
+        edgeless.add_node(Node::new(2)).expect("Failed to add node");
+
+        let scores = hits(&edgeless, &config).unwrap();
+        assert!(
+            scores.get_hubs().values().all(|&h| h == 0.0),
+            "Edgeless graph has a hub"
+        );
+        assert!(
+            scores.get_authorities().values().all(|&a| a == 0.0),
+            "Edgeless graph has an authority"
+        );
+    }
+}
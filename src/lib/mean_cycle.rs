@@ -0,0 +1,223 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::rc::Rc;
+
+/// Cycle with the smallest mean edge cost, created by minimum_mean_cycle()
+pub struct MeanCycle<T: std::cmp::PartialEq + std::hash::Hash> {
+    cycle: Vec<NodeRcWrapper<T>>,
+    mean: f64,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> MeanCycle<T> {
+    /// Nodes of the cycle in edge order, without repeating the first at the end
+    pub fn get_cycle(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.cycle
+    }
+
+    /// Total cost of the cycle divided by its number of edges
+    pub fn get_mean(&self) -> f64 {
+        self.mean
+    }
+}
+
+/// Finds the cycle of a directed graph whose edges have the smallest mean weight
+pub fn minimum_mean_cycle<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<MeanCycle<T>, AlgorithmError> {
+    minimum_mean_cycle_by(graph, &|_, _, weight| weight as f64)
+}
+
+/// Finds the cycle of a directed graph whose edges have the smallest mean cost
+///
+/// costs is called with the values of both ends of each edge and its weight, and may be
+/// negative. For exchange rates, a cost of -ln(rate) gives a negative mean exactly when
+/// trading around the cycle makes a profit, so the cycle found is the best arbitrage loop
+pub fn minimum_mean_cycle_by<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    costs: &dyn Fn(&T, &T, u32) -> f64,
+) -> Result<MeanCycle<T>, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    let nodes = graph.get_nodes();
+    let n = nodes.len();
+
+    let mut edges = Vec::new();
+    for (u, targets) in graph.get_adjacency().into_iter().enumerate() {
+        for (v, weight) in targets {
+            let cost = costs(
+                nodes[u].0.borrow().get_value(),
+                nodes[v].0.borrow().get_value(),
+                weight,
+            );
+
+            if !cost.is_finite() {
+                return Err(AlgorithmError::InvalidArgument(
+                    "Edge costs must be finite".to_string(),
+                ));
+            }
+
+            edges.push((u, v, cost));
+        }
+    }
+
+    // Karp: cheapest walk of exactly k edges into every node, starting anywhere
+    let mut walks = vec![vec![f64::INFINITY; n]; n + 1];
+    let mut parents = vec![vec![0; n]; n + 1];
+    walks[0] = vec![0.0; n];
+    for k in 1..=n {
+        for &(u, v, cost) in &edges {
+            if walks[k - 1][u] + cost < walks[k][v] {
+                walks[k][v] = walks[k - 1][u] + cost;
+                parents[k][v] = u;
+            }
+        }
+    }
+
+    // The minimum mean is min over v of max over k of (walks[n][v] - walks[k][v]) / (n - k)
+    let mut best: Option<(f64, usize)> = None;
+    for (v, &longest) in walks[n].iter().enumerate() {
+        if longest == f64::INFINITY {
+            continue;
+        }
+
+        let worst = (0..n)
+            .filter(|&k| walks[k][v] != f64::INFINITY)
+            .map(|k| (longest - walks[k][v]) / (n - k) as f64)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        if best.is_none_or(|(mean, _)| worst < mean) {
+            best = Some((worst, v));
+        }
+    }
+
+    let (_, end) = best.ok_or_else(|| {
+        AlgorithmError::InvalidGraph("Graph does not contain a cycle".to_string())
+    })?;
+
+    // The n edge walk into end repeats a node, so split it into cycles and keep the best
+    let mut walk = vec![end];
+    for k in (1..=n).rev() {
+        walk.push(parents[k][walk[walk.len() - 1]]);
+    }
+    walk.reverse();
+
+    let cost_of = |u: usize, v: usize| {
+        edges
+            .iter()
+            .filter(|e| e.0 == u && e.1 == v)
+            .map(|e| e.2)
+            .fold(f64::INFINITY, f64::min)
+    };
+
+    let mut best_cycle: Option<(f64, Vec<usize>)> = None;
+    let mut stack: Vec<usize> = Vec::new();
+    for &node in &walk {
+        if let Some(p) = stack.iter().position(|&s| s == node) {
+            let cycle = stack.split_off(p);
+            let total: f64 = (0..cycle.len())
+                .map(|i| cost_of(cycle[i], cycle[(i + 1) % cycle.len()]))
+                .sum();
+            let mean = total / cycle.len() as f64;
+
+            if best_cycle.as_ref().is_none_or(|(m, _)| mean < *m) {
+                best_cycle = Some((mean, cycle));
+            }
+        }
+        stack.push(node);
+    }
+
+    let (mean, cycle) = best_cycle.unwrap();
+
+    Ok(MeanCycle {
+        cycle: cycle
+            .into_iter()
+            .map(|i| NodeRcWrapper(Rc::clone(&nodes[i].0)))
+            .collect(),
+        mean,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node, NodeRcWrapper};
+
+    use super::{minimum_mean_cycle, minimum_mean_cycle_by};
+
+    /// Values of a cycle rotated to start at its smallest value
+    fn rotated(cycle: &[NodeRcWrapper<u32>]) -> Vec<u32> {
+        let mut values: Vec<u32> = cycle.iter().map(|n| *n.0.borrow().get_value()).collect();
+        let smallest = values
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, v)| **v)
+            .unwrap()
+            .0;
+        values.rotate_left(smallest);
+        values
+    }
+
+    #[test]
+    fn smallest_mean() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 1 has mean 3, 2 -> 3 -> 4 -> 2 has mean 2
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 4);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 2);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 2);
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr2.0.as_ref(), 3);
+
+        let cycle = minimum_mean_cycle(&graph).unwrap();
+        assert!((cycle.get_mean() - 2.0).abs() < 1e-9, "Mean is not 2");
+        assert_eq!(
+            rotated(cycle.get_cycle()),
+            vec![2, 3, 4],
+            "Cycle is not 2, 3, 4"
+        );
+
+        let mut acyclic = Graph::<u32>::new(true);
+        let node_ptr1 = acyclic.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = acyclic.add_node(Node::new(2)).expect("Failed to add node");
+        acyclic.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        assert!(
+            minimum_mean_cycle(&acyclic).is_err(),
+            "Acyclic graph has a cycle"
+        );
+    }
+
+    #[test]
+    fn arbitrage() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // Weights are exchange rates in thousandths, only 1 -> 2 -> 3 -> 1 makes money
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 900);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 800);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr1.0.as_ref(), 1450);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1100);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 1240);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 680);
+
+        let cycle =
+            minimum_mean_cycle_by(&graph, &|_, _, rate| -(rate as f64 / 1000.0).ln()).unwrap();
+        assert!(cycle.get_mean() < 0.0, "Arbitrage not found");
+        assert_eq!(
+            rotated(cycle.get_cycle()),
+            vec![1, 2, 3],
+            "Arbitrage loop is not 1, 2, 3"
+        );
+    }
+}
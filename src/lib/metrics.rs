@@ -0,0 +1,165 @@
+use crate::alg::alg::{shortest_distances, AlgorithmError};
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Distance based metrics of a connected graph, created by metrics()
+///
+/// The eccentricity of a node is its distance to the node furthest from it. The diameter
+/// and radius are the largest and smallest eccentricity, the center and periphery are the
+/// nodes that reach them
+pub struct GraphMetrics<T: std::cmp::Eq + std::hash::Hash> {
+    eccentricities: HashMap<T, u32>,
+    diameter: u32,
+    radius: u32,
+    center: Vec<NodeRcWrapper<T>>,
+    periphery: Vec<NodeRcWrapper<T>>,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> GraphMetrics<T> {
+    /// Eccentricity of every node, keyed by node value
+    pub fn get_eccentricities(&self) -> &HashMap<T, u32> {
+        &self.eccentricities
+    }
+
+    pub fn get_diameter(&self) -> u32 {
+        self.diameter
+    }
+
+    pub fn get_radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Nodes whose eccentricity equals the radius
+    pub fn get_center(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.center
+    }
+
+    /// Nodes whose eccentricity equals the diameter
+    pub fn get_periphery(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.periphery
+    }
+}
+
+/// Computes eccentricity, diameter, radius, center and periphery
+///
+/// If weighted, distances are the sum of edge weights, otherwise the number of edges
+/// (hop count). Every node must reach every other node, so directed graphs must be
+/// strongly connected
+pub fn metrics<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+    weighted: bool,
+) -> Result<GraphMetrics<T>, AlgorithmError> {
+    if graph.get_nodes().is_empty() {
+        return Err(AlgorithmError::InvalidGraph(
+            "No nodes exist in graph".to_string(),
+        ));
+    }
+
+    let mut adjacency = graph.get_adjacency();
+    if !weighted {
+        for edges in adjacency.iter_mut() {
+            for edge in edges.iter_mut() {
+                edge.1 = 1;
+            }
+        }
+    }
+
+    let mut eccentricities = Vec::with_capacity(adjacency.len());
+    for i in 0..adjacency.len() {
+        let distances = shortest_distances(&adjacency, i);
+
+        // Every node must be reachable for eccentricity to be defined
+        if distances.iter().any(|d| d.is_none()) {
+            return Err(AlgorithmError::GraphDisconnected(
+                "Not every node can reach every other node".to_string(),
+            ));
+        }
+
+        let furthest = distances.into_iter().flatten().max().unwrap();
+        eccentricities.push(furthest);
+    }
+
+    let diameter = *eccentricities.iter().max().unwrap();
+    let radius = *eccentricities.iter().min().unwrap();
+
+    let select = |target: u32| {
+        graph
+            .get_nodes()
+            .iter()
+            .zip(&eccentricities)
+            .filter(|(_, &e)| e == target)
+            .map(|(n, _)| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect()
+    };
+
+    Ok(GraphMetrics {
+        center: select(radius),
+        periphery: select(diameter),
+        eccentricities: graph.key_by_value(eccentricities),
+        diameter,
+        radius,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alg::alg::AlgorithmError;
+    use crate::graph::{Graph, Node};
+
+    use super::metrics;
+
+    #[test]
+    fn weighted_and_hops() {
+        let mut graph = Graph::<u32>::new(false);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 - 2 - 3 - 4 path, with a heavy 1 - 4 shortcut
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 10);
+
+        // Weighted, the shortcut is never used so this is a path of length 3
+        let weighted = metrics(&graph, true).unwrap();
+        assert_eq!(weighted.get_diameter(), 3, "Weighted diameter is not 3");
+        assert_eq!(weighted.get_radius(), 2, "Weighted radius is not 2");
+        assert_eq!(weighted.get_eccentricities()[&1], 3, "ecc(1) is not 3");
+        assert_eq!(weighted.get_center().len(), 2, "Center is not 2 and 3");
+        assert_eq!(
+            weighted.get_periphery().len(),
+            2,
+            "Periphery is not 1 and 4"
+        );
+
+        // Counting hops, the shortcut turns the path into a 4-cycle
+        let hops = metrics(&graph, false).unwrap();
+        assert_eq!(hops.get_diameter(), 2, "Hop diameter is not 2");
+        assert_eq!(hops.get_radius(), 2, "Hop radius is not 2");
+        assert_eq!(hops.get_center().len(), 4, "Every node is not central");
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+
+        // 1 -> 2 only, 2 cannot reach 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        assert!(
+            matches!(
+                metrics(&graph, true),
+                Err(AlgorithmError::GraphDisconnected(_))
+            ),
+            "Disconnected graph was accepted"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::alg::alg::{validate_endpoints, AlgorithmError};
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Limits on the paths returned by all_simple_paths
+///
+/// Lengths count edges and costs are the sum of edge weights, as in calculate_path_cost.
+/// Every limit is inclusive and unset by default
+pub struct SimplePathsConfig {
+    min_length: usize,
+    max_length: Option<usize>,
+    max_cost: Option<u32>,
+}
+
+impl SimplePathsConfig {
+    pub fn new() -> SimplePathsConfig {
+        SimplePathsConfig {
+            min_length: 0,
+            max_length: None,
+            max_cost: None,
+        }
+    }
+
+    pub fn set_min_length(&mut self, min_length: usize) {
+        self.min_length = min_length;
+    }
+
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = Some(max_length);
+    }
+
+    pub fn set_max_cost(&mut self, max_cost: u32) {
+        self.max_cost = Some(max_cost);
+    }
+}
+
+impl Default for SimplePathsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator over the simple paths between two nodes, created by all_simple_paths()
+///
+/// Paths are found lazily by depth-first search, which stops going deeper as soon as the
+/// maximum length or cost is passed
+pub struct SimplePaths<T: std::cmp::PartialEq + std::hash::Hash> {
+    nodes: Vec<NodeRcWrapper<T>>,
+    adjacency: Vec<Vec<(usize, u32)>>,
+    end: usize,
+    config: SimplePathsConfig,
+    on_path: Vec<bool>,
+    // Each entry is a node on the path and the position of its next edge to try
+    stack: Vec<(usize, usize)>,
+    // Sum of the edge weights on the path, only tracked when there is a maximum cost
+    cost: u32,
+}
+
+/// Returns an iterator over every path from start to end that visits no node twice
+pub fn all_simple_paths<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    start: &RefCell<Node<T>>,
+    end: &RefCell<Node<T>>,
+    config: SimplePathsConfig,
+) -> Result<SimplePaths<T>, AlgorithmError> {
+    let (start, end) = validate_endpoints(graph, start, end)?;
+    let adjacency = graph.get_adjacency();
+
+    let mut on_path = vec![false; adjacency.len()];
+    on_path[start] = true;
+
+    Ok(SimplePaths {
+        nodes: graph
+            .get_nodes()
+            .iter()
+            .map(|n| NodeRcWrapper(Rc::clone(&n.0)))
+            .collect(),
+        adjacency,
+        end,
+        config,
+        on_path,
+        stack: vec![(start, 0)],
+        cost: 0,
+    })
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> Iterator for SimplePaths<T> {
+    type Item = Vec<NodeRcWrapper<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(&(node, next)) = self.stack.last() {
+            let length = self.stack.len() - 1;
+
+            // The end is never passed through, and a full path cannot grow
+            let exhausted = node == self.end
+                || next >= self.adjacency[node].len()
+                || self.config.max_length.is_some_and(|max| length >= max);
+
+            if exhausted {
+                self.stack.pop();
+                self.on_path[node] = false;
+                if self.config.max_cost.is_some() {
+                    if let Some(&(previous, edge)) = self.stack.last() {
+                        self.cost -= self.adjacency[previous][edge - 1].1;
+                    }
+                }
+                continue;
+            }
+
+            let depth = self.stack.len();
+            self.stack[depth - 1].1 += 1;
+
+            let (to, weight) = self.adjacency[node][next];
+            if self.on_path[to] {
+                continue;
+            }
+
+            // A cost past u32::MAX is over any maximum
+            if let Some(max) = self.config.max_cost {
+                match self.cost.checked_add(weight) {
+                    Some(cost) if cost <= max => self.cost = cost,
+                    _ => continue,
+                }
+            }
+
+            self.on_path[to] = true;
+            self.stack.push((to, 0));
+
+            if to == self.end && length + 1 >= self.config.min_length {
+                return Some(
+                    self.stack
+                        .iter()
+                        .map(|&(i, _)| NodeRcWrapper(Rc::clone(&self.nodes[i].0)))
+                        .collect(),
+                );
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alg::alg::calculate_path_cost;
+    use crate::graph::{Graph, Node};
+
+    use super::{all_simple_paths, SimplePathsConfig};
+
+    fn dependency_graph() -> Graph<u32> {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 4 directly, through 2, through 3 and through 2 then 3
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 10);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 4);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 5);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        // 3 -> 2 makes a cycle the paths must not repeat
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 1);
+
+        graph
+    }
+
+    fn collect(graph: &Graph<u32>, config: SimplePathsConfig) -> Vec<Vec<u32>> {
+        let start = graph.get_nodes()[0].0.clone();
+        let end = graph.get_nodes()[3].0.clone();
+
+        let mut paths: Vec<Vec<u32>> = all_simple_paths(graph, &start, &end, config)
+            .unwrap()
+            .map(|p| p.iter().map(|n| *n.0.borrow().get_value()).collect())
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn every_path() {
+        let graph = dependency_graph();
+
+        let paths = collect(&graph, SimplePathsConfig::new());
+        assert_eq!(
+            paths,
+            vec![
+                vec![1, 2, 3, 4],
+                vec![1, 2, 4],
+                vec![1, 3, 2, 4],
+                vec![1, 3, 4],
+                vec![1, 4]
+            ],
+            "Paths incorrect"
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let graph = dependency_graph();
+
+        let mut config = SimplePathsConfig::new();
+        config.set_min_length(2);
+        config.set_max_length(2);
+        assert_eq!(
+            collect(&graph, config),
+            vec![vec![1, 2, 4], vec![1, 3, 4]],
+            "Paths of length 2 incorrect"
+        );
+
+        // Costs are 3, 6, 10, 5 and 10
+        let mut config = SimplePathsConfig::new();
+        config.set_max_cost(6);
+        let paths = collect(&graph, config);
+        assert_eq!(
+            paths,
+            vec![vec![1, 2, 3, 4], vec![1, 2, 4], vec![1, 3, 4]],
+            "Paths within cost 6 incorrect"
+        );
+
+        let start = graph.get_nodes()[0].0.clone();
+        let end = graph.get_nodes()[3].0.clone();
+        for path in all_simple_paths(&graph, &start, &end, SimplePathsConfig::new()).unwrap() {
+            if path.len() == 4 && *path[1].0.borrow().get_value() == 2 {
+                assert_eq!(calculate_path_cost(&path), 3, "Cost of 1, 2, 3, 4 is not 3");
+            }
+        }
+
+        // 1 -> 2 -> 4 costs more than u32::MAX
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        assert_eq!(
+            collect(&graph, SimplePathsConfig::new()),
+            vec![vec![1, 2, 4], vec![1, 4]],
+            "Paths without a maximum cost incorrect"
+        );
+
+        let mut config = SimplePathsConfig::new();
+        config.set_max_cost(u32::MAX);
+        assert_eq!(
+            collect(&graph, config),
+            vec![vec![1, 4]],
+            "Path past u32::MAX within maximum cost"
+        );
+    }
+}
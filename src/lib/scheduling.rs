@@ -0,0 +1,223 @@
+use crate::alg::alg::{build_path, topological_order, AlgorithmError};
+use crate::graph::{Graph, NodeRcWrapper};
+
+use std::collections::HashMap;
+
+/// Critical path schedule of a directed acyclic graph, created by schedule()
+pub struct Schedule<T: std::cmp::Eq + std::hash::Hash> {
+    length: u32,
+    earliest_starts: HashMap<T, u32>,
+    latest_starts: HashMap<T, u32>,
+    slack: HashMap<T, u32>,
+    critical_path: Vec<NodeRcWrapper<T>>,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> Schedule<T> {
+    /// Latest earliest start of any node, the length of the longest path
+    pub fn get_length(&self) -> u32 {
+        self.length
+    }
+
+    /// Earliest time every node can start, keyed by node value
+    pub fn get_earliest_starts(&self) -> &HashMap<T, u32> {
+        &self.earliest_starts
+    }
+
+    /// Latest time every node can start without delaying the schedule, keyed by node value
+    pub fn get_latest_starts(&self) -> &HashMap<T, u32> {
+        &self.latest_starts
+    }
+
+    /// How long every node can be delayed without delaying the schedule, keyed by node value
+    pub fn get_slack(&self) -> &HashMap<T, u32> {
+        &self.slack
+    }
+
+    /// Longest path through the graph, every node on it has no slack
+    pub fn get_critical_path(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.critical_path
+    }
+}
+
+/// Finds the path with the largest total weight in a directed acyclic graph
+pub fn longest_path<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<Vec<NodeRcWrapper<T>>, AlgorithmError> {
+    let pass = forward_pass(graph)?;
+
+    Ok(build_path(graph, &pass.previous, last_node(&pass.earliest)))
+}
+
+/// Computes a critical path method (CPM) schedule of a directed acyclic graph
+///
+/// Each edge weight is the time that must pass between the start of a node and the start
+/// of its successor, such as the duration of the first node. Nodes without predecessors
+/// start at 0. Add a finishing node after the last jobs to account for their duration
+pub fn schedule<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Schedule<T>, AlgorithmError> {
+    let ForwardPass {
+        adjacency,
+        order,
+        earliest,
+        previous,
+    } = forward_pass(graph)?;
+    let end = last_node(&earliest);
+    let length = earliest[end];
+
+    // Backward pass, latest starts only depend on successors
+    let mut latest = vec![length; adjacency.len()];
+    for &node in order.iter().rev() {
+        for &(next, weight) in &adjacency[node] {
+            latest[node] = latest[node].min(latest[next] - weight);
+        }
+    }
+
+    let slack: Vec<u32> = latest.iter().zip(&earliest).map(|(l, e)| l - e).collect();
+
+    Ok(Schedule {
+        length,
+        earliest_starts: graph.key_by_value(earliest),
+        latest_starts: graph.key_by_value(latest),
+        slack: graph.key_by_value(slack),
+        critical_path: build_path(graph, &previous, end),
+    })
+}
+
+/// Earliest start of every node and the predecessor that sets it
+struct ForwardPass {
+    adjacency: Vec<Vec<(usize, u32)>>,
+    order: Vec<usize>,
+    earliest: Vec<u32>,
+    previous: Vec<Option<usize>>,
+}
+
+fn forward_pass<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<ForwardPass, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    if graph.get_nodes().is_empty() {
+        return Err(AlgorithmError::InvalidGraph(
+            "No nodes exist in graph".to_string(),
+        ));
+    }
+
+    let adjacency = graph.get_adjacency();
+    let order = topological_order(&adjacency)
+        .ok_or_else(|| AlgorithmError::InvalidGraph("Graph contains a cycle".to_string()))?;
+
+    let mut earliest = vec![0u32; adjacency.len()];
+    let mut previous = vec![None; adjacency.len()];
+    for &node in &order {
+        for &(next, weight) in &adjacency[node] {
+            let start = earliest[node].checked_add(weight).ok_or_else(|| {
+                AlgorithmError::InvalidGraph("Path length exceeds u32::MAX".to_string())
+            })?;
+
+            if start > earliest[next] {
+                earliest[next] = start;
+                previous[next] = Some(node);
+            }
+        }
+    }
+
+    Ok(ForwardPass {
+        adjacency,
+        order,
+        earliest,
+        previous,
+    })
+}
+
+/// First node with the latest earliest start
+fn last_node(earliest: &[u32]) -> usize {
+    let latest = *earliest.iter().max().unwrap();
+
+    earliest.iter().position(|&e| e == latest).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{longest_path, schedule};
+
+    #[test]
+    fn critical_path() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 4 takes 7, 1 -> 3 -> 4 takes 3
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 3);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 2);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 4);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let plan = schedule(&graph).unwrap();
+        assert_eq!(plan.get_length(), 7, "Schedule length is not 7");
+        assert_eq!(
+            plan.get_earliest_starts()[&3],
+            2,
+            "Earliest start of 3 is not 2"
+        );
+        assert_eq!(
+            plan.get_latest_starts()[&3],
+            6,
+            "Latest start of 3 is not 6"
+        );
+        assert_eq!(plan.get_slack()[&3], 4, "Slack of 3 is not 4");
+        assert_eq!(plan.get_slack()[&2], 0, "Slack of 2 is not 0");
+
+        let critical: Vec<u32> = plan
+            .get_critical_path()
+            .iter()
+            .map(|n| *n.0.borrow().get_value())
+            .collect();
+        assert_eq!(critical, vec![1, 2, 4], "Critical path is not 1, 2, 4");
+
+        assert_eq!(
+            longest_path(&graph).unwrap().len(),
+            3,
+            "Longest path is not 3 nodes"
+        );
+    }
+
+    #[test]
+    fn cycle_rejected() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+
+        // 1 -> 2 -> 1
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr1.0.as_ref(), 1);
+
+        assert!(schedule(&graph).is_err(), "Cyclic graph was scheduled");
+        assert!(
+            longest_path(&graph).is_err(),
+            "Cyclic graph has a longest path"
+        );
+
+        // 1 -> 2 -> 3 is longer than u32::MAX
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), u32::MAX);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+
+        assert!(schedule(&graph).is_err(), "Overflowing graph was scheduled");
+    }
+}
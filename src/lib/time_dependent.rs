@@ -0,0 +1,467 @@
+use crate::alg::alg::{validate_endpoints, AlgorithmError};
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use priority_queue::PriorityQueue;
+
+/// Times closer than this are treated as equal
+const EPSILON: f64 = 1e-9;
+
+/// Piecewise-linear travel time of an edge as a function of departure time
+///
+/// Travel times are interpolated between breakpoints and held constant before the first
+/// and after the last. Functions are FIFO: departing later never means arriving earlier
+#[derive(Clone, Debug, PartialEq)]
+pub struct TravelTimeFunction {
+    points: Vec<(f64, f64)>,
+}
+
+impl TravelTimeFunction {
+    /// Creates a function from (departure time, travel time) breakpoints
+    ///
+    /// Departure times must be strictly increasing and travel times non-negative. Fails if
+    /// the travel time drops faster than time passes between two breakpoints, as a later
+    /// departure would then overtake an earlier one
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self, AlgorithmError> {
+        if points.is_empty() {
+            return Err(AlgorithmError::InvalidArgument(
+                "Travel time function has no breakpoints".to_string(),
+            ));
+        }
+
+        if points
+            .iter()
+            .any(|&(t, f)| !t.is_finite() || !f.is_finite() || f < 0.0)
+        {
+            return Err(AlgorithmError::InvalidArgument(
+                "Travel times must be finite and non-negative".to_string(),
+            ));
+        }
+
+        for pair in points.windows(2) {
+            let ((t1, f1), (t2, f2)) = (pair[0], pair[1]);
+
+            if t2 <= t1 {
+                return Err(AlgorithmError::InvalidArgument(
+                    "Departure times must be strictly increasing".to_string(),
+                ));
+            }
+
+            if t2 + f2 < t1 + f1 {
+                return Err(AlgorithmError::InvalidArgument(
+                    "Travel time function is not FIFO".to_string(),
+                ));
+            }
+        }
+
+        Ok(Self { points })
+    }
+
+    /// Function with the same travel time at every departure time
+    pub fn constant(travel_time: f64) -> Result<Self, AlgorithmError> {
+        Self::new(vec![(0.0, travel_time)])
+    }
+
+    /// (departure time, travel time) breakpoints in departure order
+    pub fn get_points(&self) -> &Vec<(f64, f64)> {
+        &self.points
+    }
+
+    pub fn travel_time(&self, departure: f64) -> f64 {
+        let points = &self.points;
+
+        let i = points.partition_point(|&(t, _)| t <= departure);
+        if i == 0 {
+            return points[0].1;
+        }
+        if i == points.len() {
+            return points[i - 1].1;
+        }
+
+        let ((t1, f1), (t2, f2)) = (points[i - 1], points[i]);
+        f1 + (f2 - f1) * (departure - t1) / (t2 - t1)
+    }
+
+    pub fn arrival_time(&self, departure: f64) -> f64 {
+        departure + self.travel_time(departure)
+    }
+
+    /// Departure time that arrives at arrival, the earliest one if several do
+    fn departure_for(&self, arrival: f64) -> f64 {
+        let points = &self.points;
+        let (first, last) = (points[0], points[points.len() - 1]);
+
+        if arrival <= first.0 + first.1 {
+            return arrival - first.1;
+        }
+        if arrival >= last.0 + last.1 {
+            return arrival - last.1;
+        }
+
+        // Arrival times never decrease, so the answer lies before the first breakpoint
+        // arriving at or after arrival
+        let i = points.partition_point(|&(t, f)| t + f < arrival);
+        let ((t1, f1), (t2, f2)) = (points[i - 1], points[i]);
+        let (a1, a2) = (t1 + f1, t2 + f2);
+
+        t1 + (t2 - t1) * (arrival - a1) / (a2 - a1)
+    }
+
+    /// Travel time of following self and then next without waiting in between
+    fn link(&self, next: &Self) -> Self {
+        let mut times: Vec<f64> = self.points.iter().map(|p| p.0).collect();
+
+        // The result also bends wherever it reaches a breakpoint of next
+        times.extend(next.points.iter().map(|p| self.departure_for(p.0)));
+
+        Self::from_times(times, |t| {
+            let f = self.travel_time(t);
+            f + next.travel_time(t + f)
+        })
+    }
+
+    /// Smaller of self and other at every departure time
+    fn merge(&self, other: &Self) -> Self {
+        let mut times = self.union_times(other);
+
+        // Between breakpoints both are linear, so they cross at most once
+        let crossings: Vec<f64> = times
+            .windows(2)
+            .filter_map(|pair| {
+                let (a, b) = (pair[0], pair[1]);
+                let da = self.travel_time(a) - other.travel_time(a);
+                let db = self.travel_time(b) - other.travel_time(b);
+
+                (da * db < 0.0).then(|| a + (b - a) * da / (da - db))
+            })
+            .collect();
+        times.extend(crossings);
+
+        Self::from_times(times, |t| self.travel_time(t).min(other.travel_time(t)))
+    }
+
+    /// Returns true if self is faster than other at some departure time
+    fn improves(&self, other: &Self) -> bool {
+        // Both are linear between breakpoints, so the largest gain is at one of them
+        self.union_times(other)
+            .into_iter()
+            .any(|t| self.travel_time(t) < other.travel_time(t) - EPSILON)
+    }
+
+    fn union_times(&self, other: &Self) -> Vec<f64> {
+        let mut times: Vec<f64> = self
+            .points
+            .iter()
+            .chain(&other.points)
+            .map(|p| p.0)
+            .collect();
+        times.sort_by(|a, b| a.total_cmp(b));
+        times.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+
+        times
+    }
+
+    /// Samples travel_time at times, which must include every breakpoint of the result
+    fn from_times(mut times: Vec<f64>, travel_time: impl Fn(f64) -> f64) -> Self {
+        times.sort_by(|a, b| a.total_cmp(b));
+        times.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+
+        let mut points: Vec<(f64, f64)> = Vec::with_capacity(times.len());
+        for t in times {
+            let point = (t, travel_time(t));
+
+            // Drop the last point if it lies on the line to the new one
+            if let [.., (t1, f1), (t2, f2)] = points[..] {
+                let expected = f1 + (point.1 - f1) * (t2 - t1) / (point.0 - t1);
+                if (expected - f2).abs() < EPSILON {
+                    points.pop();
+                }
+            }
+
+            points.push(point);
+        }
+
+        // Flat ends are already covered by holding the travel time constant
+        while points.len() >= 2 && (points[0].1 - points[1].1).abs() < EPSILON {
+            points.remove(0);
+        }
+        while points.len() >= 2
+            && (points[points.len() - 1].1 - points[points.len() - 2].1).abs() < EPSILON
+        {
+            points.pop();
+        }
+
+        Self { points }
+    }
+}
+
+/// Path found by earliest_arrival()
+pub struct TimedPath<T: std::cmp::PartialEq + std::hash::Hash> {
+    path: Vec<NodeRcWrapper<T>>,
+    arrival_times: Vec<f64>,
+}
+
+impl<T: std::cmp::PartialEq + std::hash::Hash> TimedPath<T> {
+    pub fn get_path(&self) -> &Vec<NodeRcWrapper<T>> {
+        &self.path
+    }
+
+    /// Time each node of the path is reached, starting with the departure time
+    pub fn get_arrival_times(&self) -> &Vec<f64> {
+        &self.arrival_times
+    }
+
+    /// Time the end node is reached
+    pub fn get_arrival(&self) -> f64 {
+        self.arrival_times[self.arrival_times.len() - 1]
+    }
+}
+
+/// Finds the path from start to end that arrives first when departing at departure
+///
+/// travel_times is called with the values of both ends of each edge and its weight, and
+/// returns the travel time function of the edge. Since every function is FIFO, waiting at
+/// a node never helps
+pub fn earliest_arrival<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    start: &RefCell<Node<T>>,
+    end: &RefCell<Node<T>>,
+    departure: f64,
+    travel_times: &dyn Fn(&T, &T, u32) -> TravelTimeFunction,
+) -> Result<TimedPath<T>, AlgorithmError> {
+    let (start, end) = validate_endpoints(graph, start, end)?;
+    let adjacency = edge_functions(graph, travel_times);
+    let n = adjacency.len();
+
+    let mut arrivals: Vec<Option<f64>> = vec![None; n];
+    let mut previous = vec![None; n];
+    let mut visited = vec![false; n];
+    arrivals[start] = Some(departure);
+
+    let mut queue = PriorityQueue::<usize, Reverse<Time>>::new();
+    queue.push(start, Reverse(Time(departure)));
+
+    while let Some((node, Reverse(Time(time)))) = queue.pop() {
+        if node == end {
+            break;
+        }
+        visited[node] = true;
+
+        for (next, function) in &adjacency[node] {
+            if visited[*next] {
+                continue;
+            }
+
+            let arrival = function.arrival_time(time);
+            if arrivals[*next].is_none_or(|a| arrival < a) {
+                arrivals[*next] = Some(arrival);
+                previous[*next] = Some(node);
+                queue.push_increase(*next, Reverse(Time(arrival)));
+            }
+        }
+    }
+
+    if arrivals[end].is_none() {
+        return Err(AlgorithmError::CannotFindPath("No path found".to_string()));
+    }
+
+    let mut order = vec![end];
+    while let Some(p) = previous[order[order.len() - 1]] {
+        order.push(p);
+    }
+    order.reverse();
+
+    let nodes = graph.get_nodes();
+    Ok(TimedPath {
+        path: order
+            .iter()
+            .map(|&i| NodeRcWrapper(Rc::clone(&nodes[i].0)))
+            .collect(),
+        arrival_times: order.iter().map(|&i| arrivals[i].unwrap()).collect(),
+    })
+}
+
+/// Travel time from start to end for every departure time
+///
+/// Returns the lower envelope of the travel time functions of all paths, so it can be
+/// evaluated at any departure time without searching again. Use earliest_arrival() to get
+/// the path for a chosen departure time
+pub fn travel_time_profile<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    start: &RefCell<Node<T>>,
+    end: &RefCell<Node<T>>,
+    travel_times: &dyn Fn(&T, &T, u32) -> TravelTimeFunction,
+) -> Result<TravelTimeFunction, AlgorithmError> {
+    let (start, end) = validate_endpoints(graph, start, end)?;
+    let adjacency = edge_functions(graph, travel_times);
+    let n = adjacency.len();
+
+    // Label correcting: a node is searched again whenever its profile improves
+    let mut profiles: Vec<Option<TravelTimeFunction>> = vec![None; n];
+    profiles[start] = Some(TravelTimeFunction {
+        points: vec![(0.0, 0.0)],
+    });
+
+    let mut queued = vec![false; n];
+    let mut queue = VecDeque::from([start]);
+    queued[start] = true;
+
+    while let Some(node) = queue.pop_front() {
+        queued[node] = false;
+        let profile = profiles[node].clone().unwrap();
+
+        for (next, function) in &adjacency[node] {
+            let candidate = profile.link(function);
+
+            profiles[*next] = match &profiles[*next] {
+                Some(current) if !candidate.improves(current) => continue,
+                Some(current) => Some(candidate.merge(current)),
+                None => Some(candidate),
+            };
+
+            if !queued[*next] {
+                queued[*next] = true;
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    profiles[end]
+        .take()
+        .ok_or_else(|| AlgorithmError::CannotFindPath("No path found".to_string()))
+}
+
+/// Travel time function of every edge, computed once
+fn edge_functions<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+    travel_times: &dyn Fn(&T, &T, u32) -> TravelTimeFunction,
+) -> Vec<Vec<(usize, TravelTimeFunction)>> {
+    let nodes = graph.get_nodes();
+
+    graph
+        .get_adjacency()
+        .into_iter()
+        .enumerate()
+        .map(|(i, edges)| {
+            edges
+                .into_iter()
+                .map(|(j, weight)| {
+                    let function = travel_times(
+                        nodes[i].0.borrow().get_value(),
+                        nodes[j].0.borrow().get_value(),
+                        weight,
+                    );
+                    (j, function)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Time that can be used as a priority
+#[derive(PartialEq)]
+struct Time(f64);
+
+impl Eq for Time {}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Time {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{earliest_arrival, travel_time_profile, TravelTimeFunction};
+
+    fn rush_hour_graph() -> Graph<u32> {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+
+        // 1 -> 2 highway, travel time given by the rush hour function
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 10);
+        // 1 -> 3 -> 2 back roads, always 15
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 5);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 10);
+
+        graph
+    }
+
+    fn travel_times(from: &u32, to: &u32, weight: u32) -> TravelTimeFunction {
+        if (*from, *to) == (1, 2) {
+            // Highway takes 10 off peak and 30 at the peak at time 10
+            TravelTimeFunction::new(vec![(0.0, 10.0), (10.0, 30.0), (30.0, 10.0)]).unwrap()
+        } else {
+            TravelTimeFunction::constant(weight as f64).unwrap()
+        }
+    }
+
+    #[test]
+    fn earliest_arrival_path() {
+        let graph = rush_hour_graph();
+        let node_ptr1 = graph.get_nodes()[0].0.clone();
+        let node_ptr2 = graph.get_nodes()[1].0.clone();
+
+        // Off peak the highway is faster
+        let path = earliest_arrival(&graph, &node_ptr1, &node_ptr2, 0.0, &travel_times).unwrap();
+        assert_eq!(path.get_path().len(), 2, "Highway not taken off peak");
+        assert_eq!(path.get_arrival(), 10.0, "Off peak arrival is not 10");
+
+        // At the peak the back roads are faster
+        let path = earliest_arrival(&graph, &node_ptr1, &node_ptr2, 10.0, &travel_times).unwrap();
+        assert_eq!(path.get_path().len(), 3, "Back roads not taken at peak");
+        assert_eq!(
+            path.get_arrival_times(),
+            &vec![10.0, 15.0, 25.0],
+            "Peak arrival times incorrect"
+        );
+    }
+
+    #[test]
+    fn profile() {
+        let graph = rush_hour_graph();
+        let node_ptr1 = graph.get_nodes()[0].0.clone();
+        let node_ptr2 = graph.get_nodes()[1].0.clone();
+
+        // The highway is used until it gets slower than 15, at times 2.5 and 25
+        let profile = travel_time_profile(&graph, &node_ptr1, &node_ptr2, &travel_times).unwrap();
+        let expected = [(0.0, 10.0), (2.5, 15.0), (25.0, 15.0), (30.0, 10.0)];
+
+        assert_eq!(
+            profile.get_points().len(),
+            expected.len(),
+            "Profile breakpoints incorrect"
+        );
+        for (&(t, f), &(et, ef)) in profile.get_points().iter().zip(&expected) {
+            assert!(
+                (t - et).abs() < 1e-9 && (f - ef).abs() < 1e-9,
+                "Profile breakpoint incorrect"
+            );
+        }
+        assert!(
+            (profile.travel_time(27.5) - 12.5).abs() < 1e-9,
+            "Profile travel time at 27.5 is not 12.5"
+        );
+
+        // Travel time falling faster than time passes is not FIFO
+        assert!(
+            TravelTimeFunction::new(vec![(0.0, 30.0), (10.0, 10.0)]).is_err(),
+            "Non FIFO function was accepted"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use crate::alg::alg::{shortest_distances, topological_order, AlgorithmError};
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::collections::HashMap;
+
+/// Reachability between every pair of nodes in a directed graph, created by reachability()
+///
+/// Stored as one bitset per node, so each query is a hash lookup and a bit test
+pub struct Reachability<T: std::cmp::Eq + std::hash::Hash> {
+    indices: HashMap<T, usize>,
+    rows: Vec<Vec<u64>>,
+}
+
+impl<T: std::cmp::Eq + std::hash::Hash> Reachability<T> {
+    /// Returns true if there is a path of at least one edge from from to to
+    ///
+    /// A node only reaches itself if it lies on a cycle. Unknown values reach nothing
+    pub fn reachable(&self, from: &T, to: &T) -> bool {
+        match (self.indices.get(from), self.indices.get(to)) {
+            (Some(&f), Some(&t)) => self.rows[f][t / 64] & (1 << (t % 64)) != 0,
+            _ => false,
+        }
+    }
+
+    /// Number of nodes reachable from from
+    pub fn count_reachable(&self, from: &T) -> usize {
+        self.indices.get(from).map_or(0, |&f| {
+            self.rows[f].iter().map(|b| b.count_ones() as usize).sum()
+        })
+    }
+}
+
+/// Builds the reachability bitsets of a directed graph
+pub fn reachability<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Reachability<T>, AlgorithmError> {
+    let adjacency = directed_adjacency(graph)?;
+
+    let indices = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.0.borrow().get_value().clone(), i))
+        .collect();
+
+    Ok(Reachability {
+        indices,
+        rows: reachable_sets(&adjacency),
+    })
+}
+
+/// Builds a new graph with an edge from every node to every node it can reach
+///
+/// Each edge is weighted with the length of the shortest path it replaces. No self loops
+/// are added for nodes on a cycle, use reachability() to ask whether a node reaches itself
+pub fn transitive_closure<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Graph<T>, AlgorithmError> {
+    let adjacency = directed_adjacency(graph)?;
+    let (mut closure, nodes) = copy_nodes(graph);
+
+    for (i, from) in nodes.iter().enumerate() {
+        let distances = shortest_distances(&adjacency, i);
+
+        for (j, to) in nodes.iter().enumerate() {
+            if let (false, Some(d)) = (i == j, distances[j]) {
+                closure.add_edge(from.0.as_ref(), to.0.as_ref(), d);
+            }
+        }
+    }
+
+    Ok(closure)
+}
+
+/// Builds a new graph with the fewest edges that keeps the reachability of a directed
+/// acyclic graph
+///
+/// An edge is kept only if there is no other path between its ends. Kept edges keep their
+/// weight. Graphs with cycles are rejected since their reduction is not unique
+pub fn transitive_reduction<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> Result<Graph<T>, AlgorithmError> {
+    let adjacency = directed_adjacency(graph)?;
+
+    if topological_order(&adjacency).is_none() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph contains a cycle".to_string(),
+        ));
+    }
+
+    let reachable = reachable_sets(&adjacency);
+    let (mut reduction, nodes) = copy_nodes(graph);
+
+    for (i, edges) in adjacency.iter().enumerate() {
+        for &(to, weight) in edges {
+            // Redundant if another child of i already reaches to
+            let redundant = edges
+                .iter()
+                .any(|&(c, _)| c != to && reachable[c][to / 64] & (1 << (to % 64)) != 0);
+
+            if !redundant {
+                reduction.add_edge(nodes[i].0.as_ref(), nodes[to].0.as_ref(), weight);
+            }
+        }
+    }
+
+    Ok(reduction)
+}
+
+/// Nodes reachable from each node by at least one edge, as bitsets
+fn reachable_sets(adjacency: &[Vec<(usize, u32)>]) -> Vec<Vec<u64>> {
+    let n = adjacency.len();
+    let words = n.div_ceil(64);
+
+    let mut rows = vec![vec![0u64; words]; n];
+    for (i, row) in rows.iter_mut().enumerate() {
+        let mut stack: Vec<usize> = adjacency[i].iter().map(|&(j, _)| j).collect();
+        while let Some(v) = stack.pop() {
+            if row[v / 64] & (1 << (v % 64)) != 0 {
+                continue;
+            }
+            row[v / 64] |= 1 << (v % 64);
+
+            for &(w, _) in &adjacency[v] {
+                stack.push(w);
+            }
+        }
+    }
+
+    rows
+}
+
+fn directed_adjacency<T: std::cmp::PartialEq + std::hash::Hash>(
+    graph: &Graph<T>,
+) -> Result<Vec<Vec<(usize, u32)>>, AlgorithmError> {
+    if !graph.is_directed() {
+        return Err(AlgorithmError::InvalidGraph(
+            "Graph must be directed".to_string(),
+        ));
+    }
+
+    Ok(graph.get_adjacency())
+}
+
+/// New directed graph holding a copy of every node, in the same order, without edges
+fn copy_nodes<T: std::cmp::Eq + std::hash::Hash + Clone>(
+    graph: &Graph<T>,
+) -> (Graph<T>, Vec<NodeRcWrapper<T>>) {
+    let mut copy = Graph::new(true);
+
+    let nodes = graph
+        .get_nodes()
+        .iter()
+        .map(|n| {
+            copy.add_node(Node::new(n.0.borrow().get_value().clone()))
+                .unwrap()
+        })
+        .collect();
+
+    (copy, nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Graph, Node};
+
+    use super::{reachability, transitive_closure, transitive_reduction};
+
+    #[test]
+    fn closure_and_reachability() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 3 -> 2 cycle, 4 isolated
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 2);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr2.0.as_ref(), 3);
+
+        let reach = reachability(&graph).unwrap();
+        assert!(reach.reachable(&1, &3), "1 does not reach 3");
+        assert!(!reach.reachable(&3, &1), "3 reaches 1");
+        assert!(
+            reach.reachable(&2, &2),
+            "2 is on a cycle but does not reach itself"
+        );
+        assert!(!reach.reachable(&1, &1), "1 reaches itself");
+        assert!(!reach.reachable(&1, &4), "1 reaches 4");
+        assert_eq!(reach.count_reachable(&1), 2, "1 does not reach 2 nodes");
+
+        let closure = transitive_closure(&graph).unwrap();
+        let node = closure.get_node(node_ptr1.0.as_ref()).unwrap();
+        let mut edges: Vec<(u32, u32)> = node
+            .0
+            .borrow()
+            .get_edges()
+            .iter()
+            .map(|e| (*e.get_node().0.borrow().get_value(), e.get_weight()))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, vec![(2, 1), (3, 3)], "Closure edges of 1 incorrect");
+
+        // 2 reaches itself through 3, but gets no self loop
+        let node = closure.get_node(node_ptr2.0.as_ref()).unwrap();
+        assert_eq!(
+            node.0.borrow().get_edges().len(),
+            1,
+            "Closure edges of 2 incorrect"
+        );
+
+        let node = closure.get_node(node_ptr4.0.as_ref()).unwrap();
+        assert!(
+            node.0.borrow().get_edges().is_empty(),
+            "Isolated node has closure edges"
+        );
+    }
+
+    #[test]
+    fn reduction() {
+        let mut graph = Graph::<u32>::new(true);
+
+        let node_ptr1 = graph.add_node(Node::new(1)).expect("Failed to add node");
+        let node_ptr2 = graph.add_node(Node::new(2)).expect("Failed to add node");
+        let node_ptr3 = graph.add_node(Node::new(3)).expect("Failed to add node");
+        let node_ptr4 = graph.add_node(Node::new(4)).expect("Failed to add node");
+
+        // 1 -> 2 -> 3 -> 4 chain plus shortcuts 1 -> 3, 1 -> 4 and 2 -> 4
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr2.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr3.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr3.0.as_ref(), 1);
+        graph.add_edge(node_ptr1.0.as_ref(), node_ptr4.0.as_ref(), 1);
+        graph.add_edge(node_ptr2.0.as_ref(), node_ptr4.0.as_ref(), 1);
+
+        let reduced = transitive_reduction(&graph).unwrap();
+        for node in reduced.get_nodes() {
+            let expected = if *node.0.borrow().get_value() == 4 {
+                0
+            } else {
+                1
+            };
+            assert_eq!(
+                node.0.borrow().get_edges().len(),
+                expected,
+                "Shortcut edge kept"
+            );
+        }
+
+        // Cycles have no unique reduction
+        graph.add_edge(node_ptr4.0.as_ref(), node_ptr1.0.as_ref(), 1);
+        assert!(
+            transitive_reduction(&graph).is_err(),
+            "Cyclic graph was reduced"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use crate::alg::alg::AlgorithmError;
+use crate::graph::{Graph, Node, NodeRcWrapper};
+
+use std::fmt;
+
+/// A boolean variable or its negation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Literal {
+    variable: usize,
+    negated: bool,
+}
+
+impl Literal {
+    /// Literal that holds when variable is true
+    pub fn positive(variable: usize) -> Literal {
+        Literal {
+            variable,
+            negated: false,
+        }
+    }
+
+    /// Literal that holds when variable is false
+    pub fn negative(variable: usize) -> Literal {
+        Literal {
+            variable,
+            negated: true,
+        }
+    }
+
+    pub fn get_variable(&self) -> usize {
+        self.variable
+    }
+
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    fn not(self) -> Literal {
+        Literal {
+            variable: self.variable,
+            negated: !self.negated,
+        }
+    }
+
+    /// Position of the literal's node in the implication graph
+    fn index(&self) -> usize {
+        2 * self.variable + self.negated as usize
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negated {
+            write!(f, "!x{}", self.variable)
+        } else {
+            write!(f, "x{}", self.variable)
+        }
+    }
+}
+
+/// 2-SAT problem over a fixed number of boolean variables
+///
+/// Every clause is an "a or b" over two literals. Constraints between pairs of
+/// variables, such as "a requires b" or "not both a and b", fit this form
+pub struct TwoSat {
+    num_variables: usize,
+    clauses: Vec<(Literal, Literal)>,
+}
+
+impl TwoSat {
+    pub fn new(num_variables: usize) -> TwoSat {
+        TwoSat {
+            num_variables,
+            clauses: Vec::new(),
+        }
+    }
+
+    pub fn get_num_variables(&self) -> usize {
+        self.num_variables
+    }
+
+    /// Requires at least one of a and b to hold
+    pub fn add_clause(&mut self, a: Literal, b: Literal) -> Result<(), AlgorithmError> {
+        if a.variable >= self.num_variables || b.variable >= self.num_variables {
+            return Err(AlgorithmError::InvalidArgument(
+                "Variable does not exist".to_string(),
+            ));
+        }
+
+        self.clauses.push((a, b));
+        Ok(())
+    }
+
+    /// Builds the directed implication graph, with a node for every literal
+    ///
+    /// Each clause a or b adds the edges !a -> b and !b -> a. Clauses that always hold,
+    /// such as x or !x, add nothing. Edge weights are 1
+    pub fn implication_graph(&self) -> Graph<Literal> {
+        let mut graph = Graph::new(true);
+
+        let nodes: Vec<NodeRcWrapper<Literal>> = (0..self.num_variables)
+            .flat_map(|v| [Literal::positive(v), Literal::negative(v)])
+            .map(|literal| graph.add_node(Node::new(literal)).unwrap())
+            .collect();
+
+        for &(a, b) in &self.clauses {
+            if a == b.not() {
+                continue;
+            }
+
+            graph.add_edge(
+                nodes[a.not().index()].0.as_ref(),
+                nodes[b.index()].0.as_ref(),
+                1,
+            );
+            graph.add_edge(
+                nodes[b.not().index()].0.as_ref(),
+                nodes[a.index()].0.as_ref(),
+                1,
+            );
+        }
+
+        graph
+    }
+
+    /// Returns a value for every variable that satisfies every clause, or None if no
+    /// assignment can
+    ///
+    /// The clauses are unsatisfiable exactly when some variable and its negation imply
+    /// each other, putting them in the same strongly connected component
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let adjacency = self.implication_graph().get_adjacency();
+        let components = strongly_connected_components(&adjacency);
+
+        // Components are numbered sinks first, so pick whichever literal comes later in
+        // topological order, it cannot imply the other
+        (0..self.num_variables)
+            .map(|v| {
+                let positive = components[Literal::positive(v).index()];
+                let negative = components[Literal::negative(v).index()];
+
+                (positive != negative).then_some(positive < negative)
+            })
+            .collect()
+    }
+}
+
+/// Tarjan's algorithm, returns the component of every node
+///
+/// Components are numbered in reverse topological order of the condensed graph
+fn strongly_connected_components(adjacency: &[Vec<(usize, u32)>]) -> Vec<usize> {
+    let n = adjacency.len();
+    let mut index = vec![None; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![0; n];
+    let mut next_index = 0;
+    let mut count = 0;
+
+    for root in 0..n {
+        if index[root].is_some() {
+            continue;
+        }
+
+        let mut calls = vec![(root, 0)];
+        index[root] = Some(next_index);
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(v, edge)) = calls.last() {
+            if edge < adjacency[v].len() {
+                calls.last_mut().unwrap().1 += 1;
+                let w = adjacency[v][edge].0;
+
+                match index[w] {
+                    None => {
+                        index[w] = Some(next_index);
+                        low[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, 0));
+                    }
+                    Some(i) if on_stack[w] => low[v] = low[v].min(i),
+                    _ => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+
+            // v is the first node of its component to be visited
+            if Some(low[v]) == index[v] {
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component[w] = count;
+                    if w == v {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    component
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Literal, TwoSat};
+
+    #[test]
+    fn satisfiable() {
+        let mut problem = TwoSat::new(3);
+
+        // x0 or x1, !x0 or x1, !x1 or x2, !x0 or !x2
+        let clauses = [
+            (Literal::positive(0), Literal::positive(1)),
+            (Literal::negative(0), Literal::positive(1)),
+            (Literal::negative(1), Literal::positive(2)),
+            (Literal::negative(0), Literal::negative(2)),
+        ];
+        for &(a, b) in &clauses {
+            problem.add_clause(a, b).unwrap();
+        }
+
+        let assignment = problem.solve().expect("Satisfiable clauses were rejected");
+        assert_eq!(assignment, vec![false, true, true], "Assignment incorrect");
+
+        let holds = |l: Literal| assignment[l.get_variable()] != l.is_negated();
+        for &(a, b) in &clauses {
+            assert!(holds(a) || holds(b), "Clause {} or {} does not hold", a, b);
+        }
+
+        // One node per literal, two edges per clause
+        let graph = problem.implication_graph();
+        assert_eq!(graph.get_nodes().len(), 6, "Graph does not have 6 nodes");
+        let edges: usize = graph
+            .get_nodes()
+            .iter()
+            .map(|n| n.0.borrow().get_edges().len())
+            .sum();
+        assert_eq!(edges, 8, "Graph does not have 8 edges");
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let mut problem = TwoSat::new(2);
+
+        // x0 requires x1 and x1 excludes x0, yet one of them must hold
+        problem
+            .add_clause(Literal::negative(0), Literal::positive(1))
+            .unwrap();
+        problem
+            .add_clause(Literal::negative(0), Literal::negative(1))
+            .unwrap();
+        problem
+            .add_clause(Literal::positive(0), Literal::positive(0))
+            .unwrap();
+        assert!(problem.solve().is_none(), "Unsatisfiable clauses solved");
+
+        assert!(
+            problem
+                .add_clause(Literal::positive(2), Literal::positive(0))
+                .is_err(),
+            "Unknown variable accepted"
+        );
+    }
+}